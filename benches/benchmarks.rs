@@ -28,6 +28,51 @@ fn encode(c: &mut Criterion) {
     });
 }
 
+fn encode_into(c: &mut Criterion) {
+    let harsh = Harsh::builder().salt(CUSTOM_SALT).build().unwrap();
+    let mut buffer = String::with_capacity(32);
+    c.bench_function("Encode into", |b| {
+        b.iter(|| {
+            buffer.clear();
+            harsh
+                .encode_into(black_box(&[1, 2, 3, 4, 5]), &mut buffer)
+                .unwrap();
+            black_box(&buffer);
+        })
+    });
+}
+
+fn encode_to_buf(c: &mut Criterion) {
+    let harsh = Harsh::builder().salt(CUSTOM_SALT).build().unwrap();
+    let mut buffer = [0; 32];
+    c.bench_function("Encode to buffer", |b| {
+        b.iter(|| black_box(harsh.encode_to_buf(black_box(&[1, 2, 3, 4, 5]), &mut buffer)))
+    });
+}
+
+fn encode_padded(c: &mut Criterion) {
+    let harsh = Harsh::builder()
+        .salt(CUSTOM_SALT)
+        .length(20)
+        .build()
+        .unwrap();
+    c.bench_function("Encode padded", |b| {
+        b.iter(|| black_box(harsh.encode(black_box(&[1, 2, 3]))))
+    });
+}
+
+fn encode_padded_to_buf(c: &mut Criterion) {
+    let harsh = Harsh::builder()
+        .salt(CUSTOM_SALT)
+        .length(20)
+        .build()
+        .unwrap();
+    let mut buffer = [0; 32];
+    c.bench_function("Encode padded to buffer", |b| {
+        b.iter(|| black_box(harsh.encode_to_buf(black_box(&[1, 2, 3]), &mut buffer)))
+    });
+}
+
 fn decode(c: &mut Criterion) {
     let harsh = Harsh::builder().salt(CUSTOM_SALT).build().unwrap();
     let encoded = harsh.encode(&[1, 2, 3, 4, 5]);
@@ -36,12 +81,45 @@ fn decode(c: &mut Criterion) {
     });
 }
 
+fn decode_into(c: &mut Criterion) {
+    let harsh = Harsh::builder().salt(CUSTOM_SALT).build().unwrap();
+    let encoded = harsh.encode(&[1, 2, 3, 4, 5]);
+    let mut values = Vec::with_capacity(5);
+    c.bench_function("Decode into", |b| {
+        b.iter(|| {
+            values.clear();
+            black_box(harsh.decode_into(black_box(&encoded), &mut values))
+        })
+    });
+}
+
+fn decode_to_slice(c: &mut Criterion) {
+    let harsh = Harsh::builder().salt(CUSTOM_SALT).build().unwrap();
+    let encoded = harsh.encode(&[1, 2, 3, 4, 5]);
+    let mut values = [0; 8];
+    c.bench_function("Decode to slice", |b| {
+        b.iter(|| {
+            black_box(
+                harsh
+                    .decode_to_slice(black_box(&encoded), &mut values)
+                    .is_ok(),
+            )
+        })
+    });
+}
+
 criterion_group!(
     benches,
     default_initialization,
     custom_initialization,
     encode,
+    encode_into,
+    encode_to_buf,
+    encode_padded,
+    encode_padded_to_buf,
     decode,
+    decode_into,
+    decode_to_slice,
 );
 
 criterion_main!(benches);
@@ -0,0 +1,64 @@
+//! The `#[derive(Hashid)]` macro for [harsh](https://docs.rs/harsh).
+//!
+//! Enable harsh's `derive` feature rather than depending on this crate
+//! directly; the generated code refers to items in `harsh`.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{parse_macro_input, parse_quote, Data, DeriveInput, Error, Fields};
+
+/// Implements `harsh::Hashid` for a struct whose fields all implement it,
+/// encoding them as one hashid in the order they are declared.
+#[proc_macro_derive(Hashid)]
+pub fn derive_hashid(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(input)
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
+fn expand(mut input: DeriveInput) -> Result<TokenStream2, Error> {
+    let fields = match input.data {
+        Data::Struct(ref data) => &data.fields,
+        _ => {
+            return Err(Error::new_spanned(
+                &input.ident,
+                "Hashid can only be derived for structs",
+            ))
+        }
+    };
+
+    let members: Vec<_> = fields.members().collect();
+    let construct = match fields {
+        Fields::Named(_) => quote!(Self { #(#members: ::harsh::Hashid::read_values(values)?,)* }),
+        Fields::Unnamed(_) => {
+            let reads = members
+                .iter()
+                .map(|_| quote!(::harsh::Hashid::read_values(values)?));
+            quote!(Self(#(#reads,)*))
+        }
+        Fields::Unit => quote!(Self),
+    };
+
+    for param in input.generics.type_params_mut() {
+        param.bounds.push(parse_quote!(::harsh::Hashid));
+    }
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics ::harsh::Hashid for #ident #ty_generics #where_clause {
+            fn push_values(&self, values: &mut ::harsh::__private::Vec<u64>) {
+                #(::harsh::Hashid::push_values(&self.#members, values);)*
+            }
+
+            fn read_values(
+                values: &mut &[u64],
+            ) -> ::core::result::Result<Self, ::harsh::DecodeError> {
+                ::core::result::Result::Ok(#construct)
+            }
+        }
+    })
+}
@@ -1,53 +1,258 @@
-use std::{error, fmt, result};
-
-use crate::{harsh::Harsh, shuffle};
-
-const DEFAULT_ALPHABET: &[u8] = b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ1234567890";
-const DEFAULT_SEPARATORS: &[u8] = b"cfhistuCFHISTU";
+use alloc::{boxed::Box, string::String, vec::Vec};
+use core::{fmt, result};
+
+use crate::{
+    blocklist::{self, DEFAULT_BLOCKLIST},
+    harsh::Harsh,
+    hashid::HashIdKind,
+    layout::Layout,
+    preset::Preset,
+    sink::MAXIMUM_WORD_LENGTH,
+};
+
+pub(crate) const DEFAULT_ALPHABET: &str =
+    "abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ1234567890";
+pub(crate) const DEFAULT_SEPARATORS: &str = "cfhistuCFHISTU";
+pub(crate) const DEFAULT_PREFIX_SEPARATOR: char = '_';
+pub(crate) const MAXIMUM_LENGTH: usize = 4096;
 
 pub type Result<T, E = BuildError> = result::Result<T, E>;
 
 /// Represents potential errors encountered during `Harsh` initialization.
 #[derive(Clone, Debug)]
+#[non_exhaustive]
 pub enum BuildError {
-    /// Error returned when the provided alphabet has insufficient distinct elements
+    /// Error returned when the provided alphabet has insufficient distinct
+    /// elements, or fewer than two are left once separators are taken out
     AlphabetLength,
 
+    /// Error returned when the provided alphabet has more than 256 distinct
+    /// characters
+    AlphabetTooLong,
+
+    /// Provided alphabet contains a character more than once, which only
+    /// `SqidsBuilder` rejects
+    DuplicateCharacter(char),
+
     /// Provided alphabet contains an illegal character
     IllegalCharacter(char),
 
-    /// Error returned when a separator character is not found in the alphabet
+    /// Provided salt is not valid UTF-8
+    Salt,
+
+    /// Error returned when the requested minimum length is too large
+    Length(usize),
+
+    /// Error returned when none of the provided separators is found in the
+    /// alphabet
     Separator,
+
+    /// Provided prefix contains a character other than a printable ASCII
+    /// character, or contains its own separator
+    Prefix(char),
+
+    /// Error returned when the prefix separator is not a printable ASCII
+    /// character, or is found in the alphabet
+    PrefixSeparator(char),
 }
 
 impl fmt::Display for BuildError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         static ALPHABET_LENGTH_MESSAGE: &str =
-            "alphabet must include at least 16 characters";
+            "alphabet does not include enough distinct characters";
+        static ALPHABET_TOO_LONG_MESSAGE: &str =
+            "alphabet includes more than 256 distinct characters";
+        static DUPLICATE_CHARACTER_MESSAGE: &str =
+            "alphabet contains a duplicate character";
         static ILLEGAL_CHARACTER_MESSAGE: &str =
             "alphabet contains an illegal character";
+        static SALT_MESSAGE: &str =
+            "salt is not valid UTF-8";
+        static LENGTH_MESSAGE: &str =
+            "minimum length is too large";
         static SEPARATOR_MESSAGE: &str =
-            "separators contain a character not found in the alphabet";
+            "separators contain no character found in the alphabet";
+        static PREFIX_MESSAGE: &str =
+            "prefix contains an illegal character";
+        static PREFIX_SEPARATOR_MESSAGE: &str =
+            "prefix separator is illegal or found in the alphabet";
 
         match self {
             BuildError::AlphabetLength => write!(f, "{}", ALPHABET_LENGTH_MESSAGE),
+            BuildError::AlphabetTooLong => write!(f, "{}", ALPHABET_TOO_LONG_MESSAGE),
+            BuildError::DuplicateCharacter(c) => {
+                write!(f, "{} ({})", DUPLICATE_CHARACTER_MESSAGE, c)
+            }
             BuildError::IllegalCharacter(c) => {
                 write!(f, "{} ({})", ILLEGAL_CHARACTER_MESSAGE, c)
             }
+            BuildError::Salt => write!(f, "{}", SALT_MESSAGE),
+            BuildError::Length(n) => write!(f, "{} ({})", LENGTH_MESSAGE, n),
             BuildError::Separator => write!(f, "{}", SEPARATOR_MESSAGE),
+            BuildError::Prefix(c) => write!(f, "{} ({})", PREFIX_MESSAGE, c),
+            BuildError::PrefixSeparator(c) => {
+                write!(f, "{} ({})", PREFIX_SEPARATOR_MESSAGE, c)
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for BuildError {}
+
+/// Represents a questionable `Harsh` configuration that nonetheless builds,
+/// as reported by [`HarshBuilder::lint`](struct.HarshBuilder.html#method.lint).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum BuildWarning {
+    /// No salt is provided, so anyone using the defaults can decode hashids
+    EmptySalt,
+
+    /// Provided salt starts or ends with whitespace, such as a newline left
+    /// over from reading it from a file
+    SaltWhitespace,
+
+    /// Provided alphabet contains a character more than once
+    DuplicateCharacter(char),
+
+    /// Provided separators contain a character more than once
+    DuplicateSeparator(char),
+
+    /// Provided separators contain characters that are not in the alphabet,
+    /// and so are ignored
+    IgnoredSeparators(Vec<char>),
+
+    /// Alphabet contains a character that must be escaped in a URL
+    UrlUnsafeCharacter(char),
+}
+
+impl fmt::Display for BuildWarning {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        static EMPTY_SALT_MESSAGE: &str =
+            "no salt is provided";
+        static SALT_WHITESPACE_MESSAGE: &str =
+            "salt starts or ends with whitespace";
+        static DUPLICATE_CHARACTER_MESSAGE: &str =
+            "alphabet contains a duplicate character";
+        static DUPLICATE_SEPARATOR_MESSAGE: &str =
+            "separators contain a duplicate character";
+        static IGNORED_SEPARATORS_MESSAGE: &str =
+            "separators contain characters not found in the alphabet";
+        static URL_UNSAFE_CHARACTER_MESSAGE: &str =
+            "alphabet contains a character that must be escaped in a URL";
+
+        match self {
+            BuildWarning::EmptySalt => write!(f, "{}", EMPTY_SALT_MESSAGE),
+            BuildWarning::SaltWhitespace => write!(f, "{}", SALT_WHITESPACE_MESSAGE),
+            BuildWarning::DuplicateCharacter(c) => {
+                write!(f, "{} ({})", DUPLICATE_CHARACTER_MESSAGE, c)
+            }
+            BuildWarning::DuplicateSeparator(c) => {
+                write!(f, "{} ({})", DUPLICATE_SEPARATOR_MESSAGE, c)
+            }
+            BuildWarning::IgnoredSeparators(chars) => {
+                write!(f, "{} (", IGNORED_SEPARATORS_MESSAGE)?;
+                chars.iter().try_for_each(|c| write!(f, "{}", c))?;
+                write!(f, ")")
+            }
+            BuildWarning::UrlUnsafeCharacter(c) => {
+                write!(f, "{} ({})", URL_UNSAFE_CHARACTER_MESSAGE, c)
+            }
+        }
+    }
+}
+
+/// A plain description of a `Harsh` configuration.
+///
+/// Every field mirrors the `HarshBuilder` method of the same name, and absent
+/// fields fall back to the builder's defaults. With the `serde` feature
+/// enabled, this may be loaded from any format supported by serde.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(::serde::Serialize, ::serde::Deserialize),
+    serde(default)
+)]
+pub struct HarshConfig {
+    pub salt: Option<String>,
+    pub alphabet: Option<String>,
+    pub separators: Option<String>,
+    pub preset: Option<Preset>,
+    pub length: usize,
+    pub blocklist: Option<Vec<String>>,
+    pub prefix: Option<String>,
+    pub prefix_separator: Option<char>,
+    pub checksum: bool,
+    pub case_insensitive: bool,
+    pub ascii_only: bool,
+}
+
+impl HarshConfig {
+    /// Initializes a new `Harsh` based on the `HarshConfig`.
+    pub fn build(self) -> Result<Harsh> {
+        HarshBuilder::from(self).build()
+    }
+}
+
+impl From<HarshConfig> for HarshBuilder {
+    fn from(config: HarshConfig) -> Self {
+        HarshBuilder {
+            salt: config.salt.map(String::into_bytes),
+            alphabet: config.alphabet.map(String::into_bytes),
+            separators: config.separators.map(String::into_bytes),
+            preset: config.preset,
+            hash_length: config.length,
+            blocklist: config.blocklist,
+            prefix: config.prefix,
+            prefix_separator: config.prefix_separator,
+            checksum: config.checksum,
+            case_insensitive: config.case_insensitive,
+            ascii_only: config.ascii_only,
         }
     }
 }
 
-impl error::Error for BuildError {}
+/// Converts the builder's settings back into a config.
+///
+/// Any salt, alphabet or separators that are not valid UTF-8 are converted
+/// lossily.
+impl From<&HarshBuilder> for HarshConfig {
+    fn from(builder: &HarshBuilder) -> Self {
+        let to_string = |bytes: &Option<Vec<u8>>| {
+            bytes
+                .as_ref()
+                .map(|bytes| String::from_utf8_lossy(bytes).into_owned())
+        };
+
+        HarshConfig {
+            salt: to_string(&builder.salt),
+            alphabet: to_string(&builder.alphabet),
+            separators: to_string(&builder.separators),
+            preset: builder.preset,
+            length: builder.hash_length,
+            blocklist: builder.blocklist.clone(),
+            prefix: builder.prefix.clone(),
+            prefix_separator: builder.prefix_separator,
+            checksum: builder.checksum,
+            case_insensitive: builder.case_insensitive,
+            ascii_only: builder.ascii_only,
+        }
+    }
+}
 
 /// A builder used to configure and create a Harsh instance.
-#[derive(Debug, Default)]
+#[derive(Clone, Debug, Default)]
 pub struct HarshBuilder {
     salt: Option<Vec<u8>>,
     alphabet: Option<Vec<u8>>,
     separators: Option<Vec<u8>>,
+    preset: Option<Preset>,
     hash_length: usize,
+    blocklist: Option<Vec<String>>,
+    prefix: Option<String>,
+    prefix_separator: Option<char>,
+    checksum: bool,
+    case_insensitive: bool,
+    ascii_only: bool,
 }
 
 impl HarshBuilder {
@@ -57,14 +262,22 @@ impl HarshBuilder {
             salt: None,
             alphabet: None,
             separators: None,
+            preset: None,
             hash_length: 0,
+            blocklist: None,
+            prefix: None,
+            prefix_separator: None,
+            checksum: false,
+            case_insensitive: false,
+            ascii_only: false,
         }
     }
 
     /// Provides a salt.
     ///
-    /// Note that this salt will be converted into a `[u8]` before use, meaning
-    /// that multi-byte utf8 character values should be avoided.
+    /// The salt must be UTF-8, and is read character by character as
+    /// hashids.js reads it, so a salt that isn't ASCII gives the same hashids
+    /// there.
     pub fn salt<T: Into<Vec<u8>>>(mut self, salt: T) -> HarshBuilder {
         self.salt = Some(salt.into());
         self
@@ -72,9 +285,10 @@ impl HarshBuilder {
 
     /// Provides an alphabet.
     ///
-    /// Note that this alphabet will be converted into a `[u8]` before use, meaning
-    /// that multi-byte utf8 character values should be avoided. The alphabet must
-    /// include at least sixteen characters.
+    /// The alphabet must be UTF-8, and include at least sixteen and at most
+    /// 256 distinct characters, which need not be ASCII. Characters repeated
+    /// in the alphabet are kept only the first time, as hashids.js does.
+    /// Minimum lengths are counted in characters rather than bytes.
     pub fn alphabet<T: Into<Vec<u8>>>(mut self, alphabet: T) -> HarshBuilder {
         self.alphabet = Some(alphabet.into());
         self
@@ -82,182 +296,359 @@ impl HarshBuilder {
 
     /// Provides a set of separators.
     ///
-    /// Note that these separators will be converted into a `[u8]` before use,
-    /// meaning that multi-byte utf8 character values should be avoided.
+    /// The separators must be UTF-8. Repeated separators and separators that
+    /// are missing from the alphabet are ignored, and [`lint`](#method.lint)
+    /// reports the latter, but building fails with `BuildError::Separator`
+    /// if every one of them is missing. At least two characters of the
+    /// alphabet must be left over, or building fails with
+    /// `BuildError::AlphabetLength`.
     pub fn separators<T: Into<Vec<u8>>>(mut self, separators: T) -> HarshBuilder {
         self.separators = Some(separators.into());
         self
     }
 
+    /// Provides an alphabet and separators from a preset, which also lets
+    /// hashids be decoded with lookalikes of some characters in place of
+    /// them.
+    ///
+    /// An alphabet or separators provided to the builder are used in place of
+    /// those of the preset, whatever order they are provided in.
+    pub fn preset(mut self, preset: Preset) -> HarshBuilder {
+        self.preset = Some(preset);
+        self
+    }
+
     /// Provides a minimum hash length.
     ///
     /// Keep in mind that hashes produced may be longer than this length.
+    /// Lengths above 4096 are rejected with `BuildError::Length`.
     pub fn length(mut self, hash_length: usize) -> HarshBuilder {
         self.hash_length = hash_length;
         self
     }
 
-    /// Initializes a new `Harsh` based on the `HarshBuilder`.
+    /// Enables filtering of hashids against a blocklist.
     ///
-    /// This method will consume the `HarshBuilder`.
-    pub fn build(self) -> Result<Harsh> {
-        const MINIMUM_ALPHABET_LENGTH: usize = 16;
+    /// Hashids containing one of the default English words or one of the
+    /// provided words, compared regardless of the case of ASCII letters, are
+    /// regenerated with the lottery character three places on, skipping the
+    /// ones that mark byte and set hashids. Letters outside ASCII are matched
+    /// in the case given. Pass an empty list to filter against the default
+    /// words only. Keep in mind that enabling the blocklist changes the output
+    /// for any value whose reference hashid is blocked. Words longer than 32
+    /// characters are ignored.
+    ///
+    /// If every one of those lottery characters leaves a blocked word in the
+    /// hashid, which takes a very small alphabet or a very broad blocklist,
+    /// `Harsh::encode` falls back to the unfiltered hashid.
+    /// `Harsh::try_encode` fails with `Error::Blocklist` instead.
+    pub fn blocklist<I, T>(mut self, words: I) -> HarshBuilder
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<String>,
+    {
+        self.blocklist = Some(words.into_iter().map(Into::into).collect());
+        self
+    }
 
-        let alphabet = unique_alphabet(&self.alphabet)?;
-        if alphabet.len() < MINIMUM_ALPHABET_LENGTH {
-            return Err(BuildError::AlphabetLength);
+    /// Provides a prefix, such as `usr`, to be written ahead of every hashid.
+    ///
+    /// The prefix and the hashid are joined by the prefix separator, so the
+    /// prefix `usr` yields hashids like `usr_jR`. Decoding a hashid that does
+    /// not start with the same prefix fails with `DecodeError::Prefix`. The
+    /// prefix may contain any printable ASCII character but its separator.
+    pub fn prefix<T: Into<String>>(mut self, prefix: T) -> HarshBuilder {
+        self.prefix = Some(prefix.into());
+        self
+    }
+
+    /// Returns a copy of this builder for ids of kind `T`, with the salt of
+    /// the kind joined to the end of the salt and the prefix of the kind, if
+    /// it has one, in place of the prefix.
+    pub fn for_kind<T: HashIdKind>(&self) -> HarshBuilder {
+        let mut builder = self.clone();
+        let salt = builder.salt.get_or_insert_with(Vec::new);
+        salt.extend_from_slice(T::SALT.as_bytes());
+        if !T::PREFIX.is_empty() {
+            builder.prefix = Some(T::PREFIX.into());
         }
+        builder
+    }
 
-        let salt = self.salt.unwrap_or_else(Vec::new);
-        let (mut alphabet, mut separators) =
-            alphabet_and_separators(&self.separators, &alphabet, &salt);
-        let guards = guards(&mut alphabet, &mut separators);
+    /// Provides the character joining the prefix to the hashid, which is `_`
+    /// by default.
+    ///
+    /// The separator must be a printable ASCII character that is not in the
+    /// alphabet, so that it can never appear in the hashid itself.
+    pub fn prefix_separator(mut self, separator: char) -> HarshBuilder {
+        self.prefix_separator = Some(separator);
+        self
+    }
 
-        Ok(Harsh::initialize(
-            alphabet.into_boxed_slice(),
-            guards.into_boxed_slice(),
-            self.hash_length,
-            salt.into_boxed_slice(),
-            separators.into_boxed_slice(),
-        ))
+    /// Appends a check character to every hashid, so that a mistyped
+    /// character is caught when decoding.
+    ///
+    /// The check character is worked out with the Luhn mod N algorithm over
+    /// every character of the alphabet, which catches any single mistyped
+    /// character and most swaps of adjacent characters. It is added on top of
+    /// the minimum length, and a hashid whose check character does not match
+    /// fails to decode with `DecodeError::Checksum`.
+    pub fn checksum(mut self, checksum: bool) -> HarshBuilder {
+        self.checksum = checksum;
+        self
+    }
+
+    /// Makes hashids case-insensitive, for ids that are read aloud or that
+    /// pass through systems that change their case.
+    ///
+    /// Only ASCII letters are folded. They are brought into lowercase in the
+    /// alphabet and separators, so an alphabet with fewer than 16 distinct
+    /// characters once their case is ignored is rejected with
+    /// `BuildError::AlphabetLength`. Hashids are always encoded with ASCII
+    /// letters in lowercase, apart from the prefix, which is written as given,
+    /// but decode with those letters in any mix of cases. Letters outside
+    /// ASCII, such as `É`, keep their case and must be typed as encoded.
+    pub fn case_insensitive(mut self, case_insensitive: bool) -> HarshBuilder {
+        self.case_insensitive = case_insensitive;
+        self
+    }
+
+    /// Requires every character of the alphabet to be ASCII, so that each
+    /// character of a hashid takes up exactly one byte.
+    ///
+    /// An alphabet with any other character is rejected with
+    /// `BuildError::IllegalCharacter`.
+    pub fn ascii_only(mut self, ascii_only: bool) -> HarshBuilder {
+        self.ascii_only = ascii_only;
+        self
     }
-}
 
-fn unique_alphabet(alphabet: &Option<Vec<u8>>) -> Result<Vec<u8>> {
-    use std::collections::HashSet;
+    /// Checks for mistakes that don't stop the `HarshBuilder` from building,
+    /// returning a warning for each one found.
+    ///
+    /// Mistakes that do, such as invisible characters in the alphabet, are
+    /// left to [`build`](#method.build) to report.
+    pub fn lint(&self) -> Vec<BuildWarning> {
+        let mut warnings = Vec::new();
+        let mut warn = |warning| {
+            if !warnings.contains(&warning) {
+                warnings.push(warning);
+            }
+        };
 
-    match *alphabet {
-        None => {
-            let mut vec = vec![0; DEFAULT_ALPHABET.len()];
-            vec.clone_from_slice(DEFAULT_ALPHABET);
-            Ok(vec)
+        match self.salt.as_deref() {
+            None | Some([]) => warn(BuildWarning::EmptySalt),
+            Some(salt) if salt.trim_ascii() != salt => warn(BuildWarning::SaltWhitespace),
+            Some(_) => {}
         }
 
-        Some(ref alphabet) => {
-            let mut reg = HashSet::new();
-            let mut ret = Vec::new();
+        let alphabet = match (&self.alphabet, self.preset) {
+            (Some(alphabet), _) => String::from_utf8_lossy(alphabet),
+            (None, Some(preset)) => preset.alphabet().into(),
+            (None, None) => DEFAULT_ALPHABET.into(),
+        };
+        let mut seen = Vec::new();
+        for c in alphabet.chars() {
+            if seen.contains(&c) {
+                warn(BuildWarning::DuplicateCharacter(c));
+            } else {
+                seen.push(c);
+            }
+        }
 
-            for &item in alphabet {
-                if item == b' ' {
-                    return Err(BuildError::IllegalCharacter(item as char));
+        if let Some(ref separators) = self.separators {
+            let fold = |c: char| {
+                if self.case_insensitive {
+                    c.to_ascii_lowercase()
+                } else {
+                    c
                 }
-
-                if !reg.contains(&item) {
-                    ret.push(item);
-                    reg.insert(item);
+            };
+            let alphabet = alphabet.chars().map(fold).collect::<Vec<_>>();
+
+            let mut seen = Vec::new();
+            let mut ignored = Vec::new();
+            for c in String::from_utf8_lossy(separators).chars() {
+                if seen.contains(&c) {
+                    warn(BuildWarning::DuplicateSeparator(c));
+                    continue;
                 }
+                seen.push(c);
+                if !alphabet.contains(&fold(c)) {
+                    ignored.push(c);
+                }
+            }
+            if !ignored.is_empty() {
+                warn(BuildWarning::IgnoredSeparators(ignored));
             }
+        }
 
-            if ret.len() < 16 {
-                Err(BuildError::AlphabetLength)
-            } else {
-                Ok(ret)
+        for c in alphabet.chars() {
+            if !c.is_ascii_alphanumeric() && !matches!(c, '-' | '.' | '_' | '~') {
+                warn(BuildWarning::UrlUnsafeCharacter(c));
             }
         }
+
+        warnings
     }
-}
 
-fn alphabet_and_separators(
-    separators: &Option<Vec<u8>>,
-    alphabet: &[u8],
-    salt: &[u8],
-) -> (Vec<u8>, Vec<u8>) {
-    const SEPARATOR_DIV: f64 = 3.5;
-
-    let separators = match *separators {
-        None => DEFAULT_SEPARATORS,
-        Some(ref separators) => separators,
-    };
-
-    let mut separators: Vec<_> = separators
-        .iter()
-        .cloned()
-        .filter(|item| alphabet.contains(item))
-        .collect();
-    let mut alphabet: Vec<_> = alphabet
-        .iter()
-        .cloned()
-        .filter(|item| !separators.contains(item))
-        .collect();
-
-    shuffle(&mut separators, salt);
-
-    if separators.is_empty() || (alphabet.len() as f64 / separators.len() as f64) > SEPARATOR_DIV {
-        let length = match (alphabet.len() as f64 / SEPARATOR_DIV).ceil() as usize {
-            1 => 2,
-            n => n,
+    /// Initializes a new `Harsh` based on the `HarshBuilder`.
+    ///
+    /// This method will consume the `HarshBuilder`.
+    pub fn build(self) -> Result<Harsh> {
+        if self.hash_length > MAXIMUM_LENGTH {
+            return Err(BuildError::Length(self.hash_length));
+        }
+
+        let salt = String::from_utf8(self.salt.unwrap_or_default())
+            .map_err(|_| BuildError::Salt)?;
+        let (alphabet, separators) = match self.preset {
+            Some(preset) => (preset.alphabet(), preset.separators()),
+            None => (DEFAULT_ALPHABET, DEFAULT_SEPARATORS),
         };
 
-        if length > separators.len() {
-            let diff = length - separators.len();
-            separators.extend_from_slice(&alphabet[..diff]);
-            alphabet = alphabet[diff..].to_vec();
-        } else {
-            separators = separators[..length].to_vec();
+        let mut layout = Layout::new(
+            self.alphabet.as_deref().unwrap_or(alphabet.as_bytes()),
+            self.separators.as_deref().unwrap_or(separators.as_bytes()),
+            &salt,
+            self.case_insensitive,
+        )?;
+
+        if let Some(preset) = self.preset {
+            layout = layout.with_aliases(preset);
         }
-    }
 
-    shuffle(&mut alphabet, salt);
-    (alphabet, separators)
-}
+        if self.ascii_only {
+            layout.check_ascii()?;
+        }
+
+        if let Some(ref separators) = self.separators {
+            layout.check_separators(separators)?;
+        }
+
+        let blocklist = match self.blocklist {
+            None => Vec::new(),
+            Some(ref words) => blocklist::filter(
+                DEFAULT_BLOCKLIST
+                    .iter()
+                    .copied()
+                    .chain(words.iter().map(String::as_str)),
+                layout.characters().iter().copied(),
+            ),
+        };
+        let blocklist = blocklist
+            .iter()
+            .map(|word| word.chars().collect::<Box<[char]>>())
+            .filter(|word| word.len() <= MAXIMUM_WORD_LENGTH)
+            .collect();
 
-fn guards(alphabet: &mut Vec<u8>, separators: &mut Vec<u8>) -> Vec<u8> {
-    const GUARD_DIV: f64 = 12.0;
-
-    let guard_count = (alphabet.len() as f64 / GUARD_DIV).ceil() as usize;
-    if alphabet.len() < 3 {
-        let guards = separators[..guard_count].to_vec();
-        separators.drain(..guard_count);
-        guards
-    } else {
-        let guards = alphabet[..guard_count].to_vec();
-        alphabet.drain(..guard_count);
-        guards
+        let prefix = self.prefix.unwrap_or_default();
+        let prefix_separator = self.prefix_separator.unwrap_or(DEFAULT_PREFIX_SEPARATOR);
+        layout.check_prefix(&prefix, prefix_separator)?;
+
+        Ok(Harsh::initialize(
+            &layout,
+            salt,
+            self.hash_length,
+            blocklist,
+            prefix,
+            prefix_separator,
+            self.checksum,
+        ))
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use alloc::string::String;
+
     #[test]
     fn alphabet_and_separator_generation() {
-        use super::{DEFAULT_ALPHABET, DEFAULT_SEPARATORS};
+        use super::{Layout, DEFAULT_ALPHABET, DEFAULT_SEPARATORS};
 
-        let (alphabet, separators) = super::alphabet_and_separators(
-            &Some(DEFAULT_SEPARATORS.to_vec()),
-            DEFAULT_ALPHABET,
-            b"this is my salt",
-        );
+        let layout = Layout::new(
+            DEFAULT_ALPHABET.as_bytes(),
+            DEFAULT_SEPARATORS.as_bytes(),
+            "this is my salt",
+            false,
+        )
+        .unwrap();
 
         assert_eq!(
             "AdG05N6y2rljDQak4xgzn8ZR1oKYLmJpEbVq3OBv9WwXPMe7",
-            alphabet.iter().map(|&u| u as char).collect::<String>()
+            layout
+                .guards()
+                .iter()
+                .chain(layout.alphabet())
+                .collect::<String>()
         );
 
         assert_eq!(
             "UHuhtcITCsFifS",
-            separators.iter().map(|&u| u as char).collect::<String>()
+            layout.separators().iter().collect::<String>()
         );
     }
 
     #[test]
     fn alphabet_and_separator_generation_with_few_separators() {
-        use super::DEFAULT_ALPHABET;
+        use super::{Layout, DEFAULT_ALPHABET};
 
-        let separators = b"fu";
-        let (alphabet, separators) = super::alphabet_and_separators(
-            &Some(separators.to_vec()),
-            DEFAULT_ALPHABET,
-            b"this is my salt",
-        );
+        let layout = Layout::new(
+            DEFAULT_ALPHABET.as_bytes(),
+            b"fu",
+            "this is my salt",
+            false,
+        )
+        .unwrap();
 
         assert_eq!(
             "4RVQrYM87wKPNSyTBGU1E6FIC9ALtH0ZD2Wxz3vs5OXJ",
-            alphabet.iter().map(|&u| u as char).collect::<String>()
+            layout
+                .guards()
+                .iter()
+                .chain(layout.alphabet())
+                .collect::<String>()
         );
 
         assert_eq!(
             "ufabcdeghijklmnopq",
-            separators.iter().map(|&u| u as char).collect::<String>()
+            layout.separators().iter().collect::<String>()
         );
     }
+
+    #[test]
+    fn guards_come_from_separators_for_small_alphabets() {
+        use super::{Layout, DEFAULT_SEPARATORS};
+
+        let layout = Layout::new(
+            b"cCsSfFhHuUiItT01",
+            DEFAULT_SEPARATORS.as_bytes(),
+            "",
+            false,
+        )
+        .unwrap();
+
+        assert_eq!("01", layout.alphabet().iter().collect::<String>());
+        assert_eq!("c", layout.guards().iter().collect::<String>());
+        assert_eq!(
+            "fhistuCFHISTU",
+            layout.separators().iter().collect::<String>()
+        );
+    }
+
+    #[test]
+    fn alphabet_must_outlast_separators() {
+        use super::{BuildError, Layout};
+
+        let alphabet = b"abcdefghijklmnop";
+        assert!(Layout::new(alphabet, b"abcdefghijklmn", "", false).is_ok());
+        assert!(matches!(
+            Layout::new(alphabet, b"abcdefghijklmno", "", false),
+            Err(BuildError::AlphabetLength)
+        ));
+        assert!(matches!(
+            Layout::new(alphabet, alphabet, "", false),
+            Err(BuildError::AlphabetLength)
+        ));
+    }
 }
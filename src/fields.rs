@@ -0,0 +1,92 @@
+use alloc::vec::Vec;
+
+use crate::{harsh::DecodeError, integer::Integer};
+
+/// A type that may be encoded as the values of a hashid.
+///
+/// This is implemented for `bool` and for the primitive integers of up to 64
+/// bits, with signed integers zigzag encoded as described for
+/// [`Integer`](trait.Integer.html). With the `derive` feature enabled,
+/// `#[derive(Hashid)]` implements it for a struct whose fields all implement
+/// it, which makes the struct's fields the values of one hashid, in the order
+/// they are declared. Fields may themselves be such structs, so newtypes
+/// around ids nest as you would expect.
+///
+/// Values are passed to [`Harsh::encode_hashid`](struct.Harsh.html#method.encode_hashid)
+/// and returned from [`Harsh::decode_hashid`](struct.Harsh.html#method.decode_hashid).
+///
+/// ```rust
+/// # #[cfg(feature = "derive")] {
+/// use harsh::{Harsh, Hashid};
+///
+/// #[derive(Debug, PartialEq, Hashid)]
+/// struct TenantId(u32);
+///
+/// #[derive(Debug, PartialEq, Hashid)]
+/// struct ItemRef {
+///     tenant: TenantId,
+///     project: u16,
+///     item: u64,
+///     archived: bool,
+/// }
+///
+/// let harsh = Harsh::default();
+/// let item = ItemRef {
+///     tenant: TenantId(7),
+///     project: 3,
+///     item: 1234,
+///     archived: false,
+/// };
+///
+/// let id = harsh.encode_hashid(&item);
+/// assert_eq!(harsh.encode(&[7, 3, 1234, 0]), id);
+/// assert_eq!(item, harsh.decode_hashid(&id).unwrap());
+/// # }
+/// ```
+pub trait Hashid: Sized {
+    /// Appends the values that make up `self` to `values`.
+    fn push_values(&self, values: &mut Vec<u64>);
+
+    /// Reads `Self` from the front of `values`, leaving the rest.
+    ///
+    /// Fails with `DecodeError::Count` if too few values are left, or with
+    /// `DecodeError::Overflow` if a value is out of range.
+    fn read_values(values: &mut &[u64]) -> Result<Self, DecodeError>;
+}
+
+/// Takes the first value from `values`.
+fn next_value(values: &mut &[u64]) -> Result<u64, DecodeError> {
+    let (&value, rest) = values.split_first().ok_or(DecodeError::Count)?;
+    *values = rest;
+    Ok(value)
+}
+
+macro_rules! integer {
+    ($($t:ty)*) => {$(
+        impl Hashid for $t {
+            fn push_values(&self, values: &mut Vec<u64>) {
+                values.push(self.to_unsigned() as u64);
+            }
+
+            fn read_values(values: &mut &[u64]) -> Result<Self, DecodeError> {
+                <$t>::from_unsigned(u128::from(next_value(values)?)).ok_or(DecodeError::Overflow)
+            }
+        }
+    )*};
+}
+
+integer!(u8 u16 u32 u64 usize i8 i16 i32 i64 isize);
+
+impl Hashid for bool {
+    fn push_values(&self, values: &mut Vec<u64>) {
+        values.push(u64::from(*self));
+    }
+
+    fn read_values(values: &mut &[u64]) -> Result<Self, DecodeError> {
+        match next_value(values)? {
+            0 => Ok(false),
+            1 => Ok(true),
+            _ => Err(DecodeError::Overflow),
+        }
+    }
+}
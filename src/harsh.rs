@@ -1,48 +1,194 @@
-use std::{error, fmt, result, str};
-
-use crate::{builder::HarshBuilder, shuffle};
+use alloc::{
+    borrow::{Cow, ToOwned},
+    boxed::Box,
+    string::String,
+    vec::Vec,
+};
+use core::{fmt, iter, result, str};
+
+use crate::{
+    builder::HarshBuilder,
+    fields::Hashid,
+    integer::Integer,
+    layout::Layout,
+    shuffle,
+    sink::{is_checksum_valid, Buffer, Checksum, Compare, Measure, Scanner, Sink, Window, Writer},
+    uuid::UuidBytes,
+};
+
+#[cfg(feature = "num-bigint")]
+use num_bigint::BigUint;
 
 type Result<T, E = Error> = result::Result<T, E>;
 
+/// Alphabets hold at most this many distinct characters.
+const MAXIMUM_ALPHABET_LENGTH: usize = 256;
+
+/// The most digits a primitive value can have when hashed, which is a `u128`
+/// in base 2.
+const MAXIMUM_DIGITS: usize = 128;
+
+/// Bytes packed into each value by `encode_bytes`, leaving the top byte of a
+/// `u128` for the bit that marks where they start.
+const BYTES_PER_VALUE: usize = 15;
+
+/// The most values `decode_set` will expand a hashid into.
+const MAXIMUM_SET_LENGTH: usize = 1 << 16;
+
+/// What a hashid's values stand for, which is marked by its lottery
+/// character so that one kind of hashid will not decode as another.
+///
+/// A hashid of frame `k` takes its lottery character `k`, `k + 3`, `k + 6`
+/// and so on places past the usual one, moving on from one to the next only
+/// when the blocklist rules it out, so plain hashids keep the usual lottery
+/// character whenever they can.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Frame {
+    Plain,
+    Bytes,
+    Set,
+}
+
+impl Frame {
+    const COUNT: usize = 3;
+
+    /// Returns the error for a hashid of another frame.
+    fn error(self) -> Error {
+        match self {
+            Frame::Plain => DecodeError::Mismatch.into(),
+            Frame::Bytes => Error::Bytes,
+            Frame::Set => Error::Set,
+        }
+    }
+}
+
 #[derive(Clone, Debug)]
+#[non_exhaustive]
 pub enum Error {
     Hex,
     Decode(DecodeError),
+    Blocklist,
+    Uuid,
+    Buffer,
+    Bytes,
+    Set,
 }
 
-#[derive(Clone, Debug)]
+/// Represents the ways in which a hashid may fail to decode.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
 pub enum DecodeError {
-    Value,
-    Hash,
+    /// The hashid is empty, or nothing follows its prefix
+    Empty,
+
+    /// The hashid contains a character that is not in the alphabet, at
+    /// `index` bytes into it
+    Character { index: usize, character: char },
+
+    /// The hashid contains a value too large for the target type
+    Overflow,
+
+    /// The hashid's guards or padding leave no value between them
+    Guards,
+
+    /// The hashid's values encode to a different hashid, so it was not
+    /// produced by this `Harsh`
+    Mismatch,
+
+    /// The hashid holds a different number of values than expected
+    Count,
+
+    /// The hashid does not start with the expected prefix
+    Prefix,
+
+    /// The hashid's check character does not match the rest of it, which
+    /// usually means that a character was mistyped
+    Checksum,
+}
+
+impl DecodeError {
+    /// Returns the byte index into the hashid of the character that is not
+    /// in the alphabet, if that is what went wrong.
+    pub fn index(&self) -> Option<usize> {
+        match *self {
+            DecodeError::Character { index, .. } => Some(index),
+            _ => None,
+        }
+    }
+
+    /// Returns the character that is not in the alphabet, if that is what
+    /// went wrong.
+    pub fn character(&self) -> Option<char> {
+        match *self {
+            DecodeError::Character { character, .. } => Some(character),
+            _ => None,
+        }
+    }
 }
 
 impl fmt::Display for DecodeError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
-            DecodeError::Value => f.write_str("Found bad value"),
-            DecodeError::Hash => f.write_str("Malformed hashid"),
+            DecodeError::Empty => f.write_str("Hashid is empty"),
+            DecodeError::Character { index, character } => write!(
+                f,
+                "Found {:?} at index {}, which is not in the alphabet",
+                character, index
+            ),
+            DecodeError::Overflow => f.write_str("Value is too large for the target type"),
+            DecodeError::Guards => f.write_str("Guards or padding leave no value"),
+            DecodeError::Mismatch => f.write_str("Values do not encode to the hashid"),
+            DecodeError::Count => f.write_str("Hashid holds an unexpected number of values"),
+            DecodeError::Prefix => f.write_str("Missing or unexpected prefix"),
+            DecodeError::Checksum => f.write_str("Checksum does not match"),
         }
     }
 }
 
-impl error::Error for DecodeError {}
+#[cfg(feature = "std")]
+impl std::error::Error for DecodeError {}
 
 impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
             Error::Hex => f.write_str("Failed to decode hex value"),
-            Error::Decode(e) => match e {
-                DecodeError::Value => f.write_str("Found bad value"),
-                DecodeError::Hash => f.write_str("Malformed hashid"),
-            },
+            Error::Blocklist => f.write_str("Every candidate hashid was blocked"),
+            Error::Uuid => f.write_str("Hashid does not contain exactly one UUID"),
+            Error::Buffer => f.write_str("Buffer is too small for hashid"),
+            Error::Bytes => f.write_str("Hashid does not contain a byte payload"),
+            Error::Set => f.write_str("Hashid does not contain a set"),
+            Error::Decode(e) => e.fmt(f),
+        }
+    }
+}
+
+impl Error {
+    /// Returns the reason a hashid failed to decode, if that is what went
+    /// wrong.
+    pub fn decode_error(&self) -> Option<&DecodeError> {
+        match self {
+            Error::Decode(e) => Some(e),
+            _ => None,
         }
     }
 }
 
-impl error::Error for Error {
-    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+impl From<DecodeError> for Error {
+    fn from(e: DecodeError) -> Error {
+        Error::Decode(e)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
         match self {
-            Error::Hex => None,
+            Error::Hex
+            | Error::Blocklist
+            | Error::Uuid
+            | Error::Buffer
+            | Error::Bytes
+            | Error::Set => None,
             Error::Decode(ref e) => Some(e),
         }
     }
@@ -53,13 +199,23 @@ impl error::Error for Error {
 /// It's probably not a great idea to use the default, because in that case
 /// your values will be entirely trivial to decode. On the other hand, this is
 /// not intended to be cryptographically-secure, so go nuts!
+///
+/// Instances built at runtime own their configuration. The
+/// [`harsh!`](macro.harsh.html) macro instead builds one at compile time,
+/// borrowing its configuration from static data.
 #[derive(Clone, Debug)]
 pub struct Harsh {
-    alphabet: Box<[u8]>,
-    guards: Box<[u8]>,
+    alphabet: Cow<'static, [char]>,
+    guards: Cow<'static, [char]>,
     hash_length: usize,
-    salt: Box<[u8]>,
-    separators: Box<[u8]>,
+    salt: Cow<'static, str>,
+    separators: Cow<'static, [char]>,
+    blocklist: Cow<'static, [Box<[char]>]>,
+    prefix: Cow<'static, str>,
+    prefix_separator: char,
+    checksum: bool,
+    case_insensitive: bool,
+    fold: Cow<'static, [u8]>,
 }
 
 impl Harsh {
@@ -76,142 +232,615 @@ impl Harsh {
     }
 
     pub(crate) fn initialize(
-        alphabet: Box<[u8]>,
-        guards: Box<[u8]>,
+        layout: &Layout,
+        salt: String,
         hash_length: usize,
-        salt: Box<[u8]>,
-        separators: Box<[u8]>,
+        blocklist: Vec<Box<[char]>>,
+        prefix: String,
+        prefix_separator: char,
+        checksum: bool,
     ) -> Self {
         Harsh {
-            alphabet,
-            guards,
+            alphabet: Cow::Owned(layout.alphabet().to_vec()),
+            guards: Cow::Owned(layout.guards().to_vec()),
             hash_length,
-            salt,
-            separators,
+            salt: Cow::Owned(salt),
+            separators: Cow::Owned(layout.separators().to_vec()),
+            blocklist: Cow::Owned(blocklist),
+            prefix: Cow::Owned(prefix),
+            prefix_separator,
+            checksum,
+            case_insensitive: layout.is_case_insensitive(),
+            fold: Cow::Owned(layout.fold().to_vec()),
         }
     }
 
+    /// Used by the `harsh!` macro.
+    #[doc(hidden)]
+    pub const fn from_layout(
+        layout: &'static Layout,
+        salt: &'static str,
+        hash_length: usize,
+        prefix: &'static str,
+        prefix_separator: char,
+        checksum: bool,
+    ) -> Self {
+        Harsh {
+            alphabet: Cow::Borrowed(layout.alphabet()),
+            guards: Cow::Borrowed(layout.guards()),
+            hash_length,
+            salt: Cow::Borrowed(salt),
+            separators: Cow::Borrowed(layout.separators()),
+            blocklist: Cow::Borrowed(&[]),
+            prefix: Cow::Borrowed(prefix),
+            prefix_separator,
+            checksum,
+            case_insensitive: layout.is_case_insensitive(),
+            fold: Cow::Borrowed(layout.fold()),
+        }
+    }
+}
+
+impl Harsh {
     /// Encodes a slice of `u64` values into a single hashid.
+    ///
+    /// When a blocklist is configured, a hashid containing a blocked word is
+    /// regenerated with the lottery character three places on, and so on
+    /// until a clean hashid is found, since the characters in between mark
+    /// byte and set hashids. A third of the alphabet is tried this way. In
+    /// the unlikely event that every candidate is blocked, the unfiltered
+    /// hashid is returned; use
+    /// [`try_encode`](#method.try_encode) to be told instead.
     pub fn encode(&self, values: &[u64]) -> String {
+        self.encode_values(values, Frame::Plain)
+    }
+
+    /// Encodes a slice of `u64` values into a single hashid, failing with
+    /// `Error::Blocklist` if every candidate hashid contains a blocked word.
+    ///
+    /// Without a blocklist, this always produces the same hashid as
+    /// [`encode`](#method.encode).
+    pub fn try_encode(&self, values: &[u64]) -> Result<String> {
         if values.is_empty() {
-            return String::new();
+            return Ok(String::new());
         }
 
         let nhash = create_nhash(values);
+        let offset = self
+            .lottery_offset(values, nhash, Frame::Plain)
+            .ok_or(Error::Blocklist)?;
 
-        let mut alphabet = self.alphabet.clone();
         let mut buffer = String::new();
+        self.write_hashid(values, nhash, offset, &mut Writer(&mut buffer))
+            .expect("writing to a String cannot fail");
+        Ok(buffer)
+    }
+
+    /// Encodes a slice of `u128` values into a single hashid.
+    ///
+    /// Values that fit in a `u64` produce the same hashids as
+    /// [`encode`](#method.encode); larger values are hashed with the same
+    /// algorithm carried out in 128-bit arithmetic.
+    pub fn encode_u128(&self, values: &[u128]) -> String {
+        self.encode_values(values, Frame::Plain)
+    }
 
-        let idx = (nhash % alphabet.len() as u64) as usize;
-        let lottery = alphabet[idx];
-        buffer.push(lottery as char);
+    /// Encodes a slice of arbitrarily large values into a single hashid.
+    ///
+    /// Values that fit in a `u128` produce the same hashids as
+    /// [`encode_u128`](#method.encode_u128); larger values are hashed with
+    /// the same algorithm, and their hashids grow with them.
+    #[cfg(feature = "num-bigint")]
+    pub fn encode_biguint(&self, values: &[BigUint]) -> String {
+        self.encode_values(values, Frame::Plain)
+    }
 
-        for (idx, &value) in values.iter().enumerate() {
-            let mut value = value;
-            let mut temp = Vec::with_capacity(self.salt.len() + alphabet.len() + 1);
-            temp.push(lottery);
-            temp.extend_from_slice(&self.salt);
-            temp.extend_from_slice(&alphabet);
+    /// Encodes a slice of `i64` values into a single hashid.
+    ///
+    /// Values are zigzag encoded first, as described for
+    /// [`Integer`](./trait.Integer.html), so `-1` and `u64::MAX` produce
+    /// different hashids.
+    pub fn encode_i64(&self, values: &[i64]) -> String {
+        self.encode_integers(values)
+    }
+
+    /// Encodes a slice of any primitive integer type into a single hashid.
+    ///
+    /// Unsigned values produce the same hashids as
+    /// [`encode_u128`](#method.encode_u128), and signed values are zigzag
+    /// encoded first, as described for [`Integer`](./trait.Integer.html).
+    pub fn encode_integers<I: Integer>(&self, values: &[I]) -> String {
+        let values: Vec<u128> = values.iter().map(|value| value.to_unsigned()).collect();
+        self.encode_values(&values, Frame::Plain)
+    }
 
-            let alphabet_len = alphabet.len();
-            shuffle(&mut alphabet, &temp[..alphabet_len]);
+    /// Writes the hashid for a slice of `u64` values to `writer`.
+    ///
+    /// This produces the same hashid as [`encode`](#method.encode) without
+    /// allocating; every intermediate buffer lives on the stack.
+    pub fn encode_into<W: fmt::Write + ?Sized>(
+        &self,
+        values: &[u64],
+        writer: &mut W,
+    ) -> fmt::Result {
+        self.write_values(values, Frame::Plain, &mut Writer(writer))
+    }
 
-            let last = hash(value, &alphabet);
-            buffer.push_str(&last);
+    /// Writes the hashid for a slice of `u64` values to `buffer` as UTF-8,
+    /// returning the number of bytes written.
+    ///
+    /// Like [`encode_into`](#method.encode_into), this does not allocate. If
+    /// the hashid does not fit, `Error::Buffer` is returned and the contents
+    /// of `buffer` are unspecified.
+    pub fn encode_to_buf(&self, values: &[u64], buffer: &mut [u8]) -> Result<usize> {
+        let mut sink = Buffer { buffer, len: 0 };
+        match self.write_values(values, Frame::Plain, &mut sink) {
+            Ok(()) => Ok(sink.len),
+            Err(_) => Err(Error::Buffer),
+        }
+    }
 
-            if idx + 1 < values.len() {
-                value %= (last.bytes().next().unwrap_or(0) as usize + idx) as u64;
-                buffer
-                    .push(self.separators[(value % self.separators.len() as u64) as usize] as char);
-            }
+    fn encode_values<V: Value>(&self, values: &[V], frame: Frame) -> String {
+        let mut buffer = String::new();
+        self.write_values(values, frame, &mut Writer(&mut buffer))
+            .expect("writing to a String cannot fail");
+        buffer
+    }
+
+    fn write_values<V: Value, S: Sink>(
+        &self,
+        values: &[V],
+        frame: Frame,
+        sink: &mut S,
+    ) -> fmt::Result {
+        if values.is_empty() {
+            return Ok(());
+        }
+
+        let nhash = create_nhash(values);
+        let offset = self
+            .lottery_offset(values, nhash, frame)
+            .unwrap_or(frame as usize);
+        self.write_hashid(values, nhash, offset, sink)
+    }
+
+    /// Returns how many places past the usual one the lottery character has
+    /// to move for a hashid of `frame` to contain no blocked word, or `None`
+    /// if every lottery character of the frame leaves one in it.
+    fn lottery_offset<V: Value>(&self, values: &[V], nhash: u64, frame: Frame) -> Option<usize> {
+        if self.blocklist.is_empty() {
+            return Some(frame as usize);
+        }
+
+        (frame as usize..self.alphabet.len())
+            .step_by(Frame::COUNT)
+            .find(|&offset| {
+                let mut scanner = Scanner::new(&self.blocklist);
+                self.write_checked(values, nhash, offset, &mut scanner)
+                    .is_ok()
+            })
+    }
+
+    /// Returns the frame of a hashid holding `values`, whose lottery
+    /// character is `lottery`.
+    fn frame_of<V: Value>(&self, values: &[V], lottery: char) -> Option<Frame> {
+        let length = self.alphabet.len();
+        let index = self.alphabet.iter().position(|&c| c == lottery)?;
+        let usual = (create_nhash(values) % length as u64) as usize;
+
+        match (index + length - usual) % length % Frame::COUNT {
+            0 => Some(Frame::Plain),
+            1 => Some(Frame::Bytes),
+            _ => Some(Frame::Set),
+        }
+    }
+
+    /// Writes the prefix, if there is one, followed by the hashid.
+    fn write_hashid<V: Value, S: Sink>(
+        &self,
+        values: &[V],
+        nhash: u64,
+        offset: usize,
+        sink: &mut S,
+    ) -> fmt::Result {
+        if !self.prefix.is_empty() {
+            self.prefix.chars().try_for_each(|c| sink.push(c))?;
+            sink.push(self.prefix_separator)?;
+        }
+
+        self.write_checked(values, nhash, offset, sink)
+    }
+
+    /// Writes a hashid with `write_with_offset`, followed by its check
+    /// character if checksums are enabled.
+    fn write_checked<V: Value, S: Sink>(
+        &self,
+        values: &[V],
+        nhash: u64,
+        offset: usize,
+        sink: &mut S,
+    ) -> fmt::Result {
+        if !self.checksum {
+            return self.write_with_offset(values, nhash, offset, sink);
         }
 
-        if buffer.len() < self.hash_length {
-            let guard_index = (nhash as usize
-                + buffer.bytes().next().expect("hellfire and damnation") as usize)
-                % self.guards.len();
-            let guard = self.guards[guard_index];
-            buffer.insert(0, guard as char);
+        let tables = self.checksum_tables();
+        let mut checksum = Checksum::new(sink, &tables);
+        self.write_with_offset(values, nhash, offset, &mut checksum)?;
+        checksum.finish()
+    }
+
+    /// The characters a hashid may contain, in the order that gives them
+    /// their code points for the checksum.
+    fn checksum_tables(&self) -> [&[char]; 3] {
+        [&self.alphabet, &self.separators, &self.guards]
+    }
 
-            if buffer.len() < self.hash_length {
-                let guard_index = (nhash as usize
-                    + buffer.as_bytes()[2] as usize)
-                    % self.guards.len();
-                let guard = self.guards[guard_index];
-                buffer.push(guard as char);
+    /// Writes a hashid whose lottery character is `offset` places past the
+    /// usual one.
+    ///
+    /// Padding wraps the guarded hashid in halves of ever more shuffled
+    /// alphabets and then trims it, from both ends, to the minimum length.
+    /// Rather than build and trim the whole thing, the unpadded hashid is
+    /// measured first, which tells us how many rounds of padding are needed
+    /// and how many characters to skip.
+    fn write_with_offset<V: Value, S: Sink>(
+        &self,
+        values: &[V],
+        nhash: u64,
+        offset: usize,
+        sink: &mut S,
+    ) -> fmt::Result {
+        let length = self.alphabet.len();
+        let lottery = self.alphabet[((nhash % length as u64) as usize + offset) % length];
+
+        let mut alphabet = ['\0'; MAXIMUM_ALPHABET_LENGTH];
+        let alphabet = &mut alphabet[..length];
+
+        if self.hash_length == 0 {
+            return self.write_core(values, lottery, alphabet, sink);
+        }
+
+        let mut measure = Measure::new();
+        self.write_core(values, lottery, alphabet, &mut measure)?;
+
+        if measure.len >= self.hash_length {
+            return self.write_core(values, lottery, alphabet, sink);
+        }
+
+        let guard = |c: char| self.guards[(nhash as usize + c as usize) % self.guards.len()];
+        let trailing_guard = if measure.len + 1 < self.hash_length {
+            Some(guard(measure.get(1)))
+        } else {
+            None
+        };
+
+        let half_length = length / 2;
+        let mut rounds = 0;
+        let mut total = measure.len + 1 + trailing_guard.is_some() as usize;
+        while total < self.hash_length {
+            rounds += 1;
+            total += length;
+        }
+
+        let excess = total.saturating_sub(self.hash_length);
+        let mut window = Window {
+            sink,
+            skip: excess / 2,
+            take: total - excess,
+        };
+
+        // The alphabet is left as it was after the last value was hashed,
+        // which is where padding starts from.
+        let mut padding = ['\0'; MAXIMUM_ALPHABET_LENGTH];
+        let padding = &mut padding[..length];
+
+        for round in (1..=rounds).rev() {
+            padding.copy_from_slice(alphabet);
+            for _ in 0..round {
+                shuffle_alphabet(padding);
             }
+            window.push_all(&padding[half_length..])?;
         }
 
-        let half_length = alphabet.len() / 2;
-        while buffer.len() < self.hash_length {
-            {
-                let alphabet_copy = alphabet.clone();
-                shuffle(&mut alphabet, &alphabet_copy);
+        window.push(guard(lottery))?;
+        match measure.retained() {
+            Some(hash) => window.push_all(hash)?,
+            None => {
+                let mut scratch = ['\0'; MAXIMUM_ALPHABET_LENGTH];
+                self.write_core(values, lottery, &mut scratch[..length], &mut window)?;
             }
+        }
+        if let Some(guard) = trailing_guard {
+            window.push(guard)?;
+        }
 
-            let (left, right) = alphabet.split_at(half_length);
-            buffer = format!(
-                "{}{}{}",
-                String::from_utf8_lossy(right),
-                buffer,
-                String::from_utf8_lossy(left)
-            );
-
-            let excess = buffer.len() as i32 - self.hash_length as i32;
-            if excess > 0 {
-                let marker = excess as usize / 2;
-                buffer = buffer[marker..marker + self.hash_length].to_owned();
+        // The last round of left padding leaves the first padding alphabet.
+        for round in 1..=rounds {
+            if round > 1 {
+                shuffle_alphabet(padding);
             }
+            window.push_all(&padding[..half_length])?;
         }
 
-        buffer
+        Ok(())
+    }
+
+    /// Writes the lottery character followed by each hashed value, leaving
+    /// `alphabet` shuffled as it was for the last value.
+    fn write_core<V: Value, S: Sink>(
+        &self,
+        values: &[V],
+        lottery: char,
+        alphabet: &mut [char],
+        sink: &mut S,
+    ) -> fmt::Result {
+        alphabet.copy_from_slice(&self.alphabet);
+        sink.push(lottery)?;
+
+        let mut salt = [0; MAXIMUM_ALPHABET_LENGTH];
+        let salt = &mut salt[..alphabet.len()];
+
+        for (idx, value) in values.iter().enumerate() {
+            fill_salt(salt, lottery, &self.salt, alphabet);
+            shuffle(alphabet, salt);
+            let first = hash(value.clone(), alphabet, sink)?;
+
+            if idx + 1 < values.len() {
+                let value = value.rem((first as usize + idx) as u64);
+                sink.push(self.separators[(value % self.separators.len() as u64) as usize])?;
+            }
+        }
+
+        Ok(())
     }
 
     /// Decodes a single hashid into a slice of `u64` values.
+    ///
+    /// The values are re-encoded to verify the hashid, so a hashid that was
+    /// rejected by the blocklist will not decode; only its regenerated
+    /// replacement will, and any other is rejected with
+    /// `DecodeError::Mismatch`. When a prefix is configured, a hashid that
+    /// does not start with it is rejected with `DecodeError::Prefix`, and
+    /// when checksums are enabled, a hashid whose check character does not
+    /// match is rejected with `DecodeError::Checksum`. A character outside
+    /// the alphabet is reported, along with its index, before either is
+    /// checked.
     pub fn decode<T: AsRef<str>>(&self, input: T) -> Result<Vec<u64>> {
-        let mut value = input.as_ref().as_bytes();
+        self.decode_values(input.as_ref(), Frame::Plain)
+    }
+
+    /// Decodes a single hashid, appending its values to `values`.
+    ///
+    /// Apart from growing `values`, this does not allocate. If the hashid
+    /// fails to decode, `values` is left as it was.
+    pub fn decode_into<T: AsRef<str>>(&self, input: T, values: &mut Vec<u64>) -> Result<()> {
+        let input = input.as_ref();
+        let start = values.len();
+
+        let result = self
+            .read_values(input, |value| {
+                values.push(value);
+                Ok(())
+            })
+            .and_then(|lottery| self.verify(input, &values[start..], lottery, Frame::Plain));
 
-        if let Some(guard_idx) = value.iter().position(|u| self.guards.contains(u)) {
-            value = &value[(guard_idx + 1)..];
+        if result.is_err() {
+            values.truncate(start);
         }
+        result
+    }
+
+    /// Decodes a single hashid into `values`, returning the part of `values`
+    /// that was filled.
+    ///
+    /// This does not allocate. A hashid holding more values than will fit is
+    /// rejected with `Error::Buffer`.
+    pub fn decode_to_slice<'a, T: AsRef<str>>(
+        &self,
+        input: T,
+        values: &'a mut [u64],
+    ) -> Result<&'a [u64]> {
+        let input = input.as_ref();
+        let mut len = 0;
+
+        let lottery = self.read_values(input, |value| {
+            *values.get_mut(len).ok_or(Error::Buffer)? = value;
+            len += 1;
+            Ok(())
+        })?;
+
+        let values = &values[..len];
+        self.verify(input, values, lottery, Frame::Plain)?;
+        Ok(values)
+    }
+
+    /// Decodes a single hashid into a vector of `u128` values.
+    ///
+    /// Values too large for a `u128` are reported as `DecodeError::Overflow`.
+    pub fn decode_u128<T: AsRef<str>>(&self, input: T) -> Result<Vec<u128>> {
+        self.decode_values(input.as_ref(), Frame::Plain)
+    }
 
-        if let Some(guard_idx) = value.iter().rposition(|u| self.guards.contains(u)) {
+    /// Decodes a single hashid into a vector of arbitrarily large values.
+    #[cfg(feature = "num-bigint")]
+    pub fn decode_biguint<T: AsRef<str>>(&self, input: T) -> Result<Vec<BigUint>> {
+        self.decode_values(input.as_ref(), Frame::Plain)
+    }
+
+    /// Decodes a hashid produced by `encode_i64` into a vector of `i64`
+    /// values.
+    ///
+    /// Values that do not map back to an `i64` are reported as
+    /// `DecodeError::Overflow`.
+    ///
+    /// Signed hashids are plain hashids of zigzag encoded values, so nothing
+    /// marks them apart: a hashid from [`encode`](#method.encode) decodes
+    /// here too, as the values it would stand for once zigzag encoded, and
+    /// `encode(&[3])` decodes as `[-2]`. Give signed ids a prefix or a salt of
+    /// their own if the two must not be confused.
+    pub fn decode_i64<T: AsRef<str>>(&self, input: T) -> Result<Vec<i64>> {
+        self.decode_integers(input)
+    }
+
+    /// Decodes a hashid produced by `encode_integers` into a vector of
+    /// integers of type `I`.
+    ///
+    /// Values that do not map back to an `I` are reported as
+    /// `DecodeError::Overflow`. As with [`decode_i64`](#method.decode_i64),
+    /// signed types read any plain hashid as zigzag encoded values.
+    pub fn decode_integers<I: Integer, T: AsRef<str>>(&self, input: T) -> Result<Vec<I>> {
+        self.decode_values::<u128>(input.as_ref(), Frame::Plain)?
+            .into_iter()
+            .map(|value| I::from_unsigned(value).ok_or(Error::Decode(DecodeError::Overflow)))
+            .collect()
+    }
+
+    fn decode_values<V: Value>(&self, input: &str, frame: Frame) -> Result<Vec<V>> {
+        let mut values = Vec::new();
+        let lottery = self.read_values(input, |value| {
+            values.push(value);
+            Ok(())
+        })?;
+        self.verify(input, &values, lottery, frame)?;
+        Ok(values)
+    }
+
+    /// Reads each value from a hashid and passes it to `push`, returning the
+    /// hashid's lottery character.
+    ///
+    /// Nothing but the hashid's prefix, characters, check character and
+    /// guards is checked here; see `verify`.
+    fn read_values<V, F>(&self, input: &str, mut push: F) -> Result<char>
+    where
+        V: Value,
+        F: FnMut(V) -> Result<()>,
+    {
+        if input.is_empty() {
+            return Err(DecodeError::Empty.into());
+        }
+
+        let mut value = self.strip_prefix(input)?;
+        if value.is_empty() {
+            return Err(DecodeError::Empty.into());
+        }
+
+        let offset = input.len() - value.len();
+        let is_character = |c| {
+            self.alphabet.contains(&c) || self.separators.contains(&c) || self.guards.contains(&c)
+        };
+        if let Some((index, character)) = value
+            .char_indices()
+            .find(|&(_, c)| !is_character(self.fold(c)))
+        {
+            return Err(DecodeError::Character {
+                index: offset + index,
+                character,
+            }
+            .into());
+        }
+
+        if self.checksum {
+            if !is_checksum_valid(&self.checksum_tables(), value, |c| self.fold(c)) {
+                return Err(DecodeError::Checksum.into());
+            }
+            if let Some((idx, _)) = value.char_indices().next_back() {
+                value = &value[..idx];
+            }
+        }
+
+        let is_guard = |c: char| self.guards.contains(&self.fold(c));
+        if let Some((guard_idx, guard)) = value.char_indices().find(|&(_, c)| is_guard(c)) {
+            value = &value[(guard_idx + guard.len_utf8())..];
+        }
+
+        if let Some(guard_idx) = value.rfind(is_guard) {
             value = &value[..guard_idx];
         }
 
-        if value.len() < 2 {
-            return Err(Error::Decode(DecodeError::Hash));
+        let mut chars = value.chars();
+        let lottery = match chars.next() {
+            Some(lottery) if !chars.as_str().is_empty() => self.fold(lottery),
+            _ => return Err(DecodeError::Guards.into()),
+        };
+        let value = chars.as_str();
+
+        let length = self.alphabet.len();
+        let mut alphabet = ['\0'; MAXIMUM_ALPHABET_LENGTH];
+        let alphabet = &mut alphabet[..length];
+        alphabet.copy_from_slice(&self.alphabet);
+
+        let mut salt = [0; MAXIMUM_ALPHABET_LENGTH];
+        let salt = &mut salt[..length];
+
+        for segment in value.split(|c| self.separators.contains(&self.fold(c))) {
+            fill_salt(salt, lottery, &self.salt, alphabet);
+            shuffle(alphabet, salt);
+            push(unhash(segment, alphabet, |c| self.fold(c)).ok_or(DecodeError::Overflow)?)?;
         }
 
-        let mut alphabet = self.alphabet.clone();
+        Ok(lottery)
+    }
 
-        let lottery = value[0];
-        let value = &value[1..];
-        let segments = value.split(|u| self.separators.contains(u));
+    /// Returns the character of the alphabet that `c` is read as, which
+    /// differs from `c` only in case-insensitive mode or for the lookalikes
+    /// of a preset.
+    fn fold(&self, c: char) -> char {
+        match self.fold.get(c as usize) {
+            Some(&u) => u as char,
+            None => c,
+        }
+    }
 
-        let result: Option<Vec<_>> = segments
-            .into_iter()
-            .map(|segment| {
-                let mut buffer = Vec::with_capacity(self.salt.len() + alphabet.len() + 1);
-                buffer.push(lottery);
-                buffer.extend_from_slice(&self.salt);
-                buffer.extend_from_slice(&alphabet);
-
-                let alphabet_len = alphabet.len();
-                shuffle(&mut alphabet, &buffer[..alphabet_len]);
-                unhash(segment, &alphabet)
-            })
-            .collect();
+    /// Removes the prefix and its separator from the front of `input`.
+    fn strip_prefix<'a>(&self, input: &'a str) -> Result<&'a str> {
+        if self.prefix.is_empty() {
+            return Ok(input);
+        }
 
-        match result {
-            None => Err(Error::Decode(DecodeError::Value)),
-            Some(result) => {
-                if self.encode(&result) == input.as_ref() {
-                    Ok(result)
-                } else {
-                    Err(Error::Decode(DecodeError::Hash))
-                }
+        let is_match =
+            |a: &[u8], b: &[u8]| a == b || self.case_insensitive && a.eq_ignore_ascii_case(b);
+
+        // The prefix and its separator are ASCII, so once they match, what
+        // follows them starts on a character boundary.
+        let len = self.prefix.len();
+        let bytes = input.as_bytes();
+        match (bytes.get(..len), bytes.get(len..=len)) {
+            (Some(prefix), Some(separator))
+                if is_match(prefix, self.prefix.as_bytes())
+                    && is_match(separator, &[self.prefix_separator as u8]) =>
+            {
+                Ok(&input[(len + 1)..])
             }
+            _ => Err(DecodeError::Prefix.into()),
+        }
+    }
+
+    /// Checks that `values`, read from `input` along with its `lottery`
+    /// character, make up a hashid of `frame` and encode to `input`, which
+    /// covers the lottery character, separators, guards and padding alike.
+    ///
+    /// The encoding is compared as it is produced rather than collected, so
+    /// a mismatch is caught at the first character that differs.
+    fn verify<V: Value>(
+        &self,
+        input: &str,
+        values: &[V],
+        lottery: char,
+        frame: Frame,
+    ) -> Result<()> {
+        if self.frame_of(values, lottery) != Some(frame) {
+            return Err(frame.error());
+        }
+
+        let mut compare = Compare::new(input, |expected, c| {
+            expected == c
+                || self.fold(expected) == c
+                || self.case_insensitive && expected.eq_ignore_ascii_case(&c)
+        });
+        match self.write_values(values, frame, &mut compare) {
+            Ok(()) if compare.is_complete() => Ok(()),
+            _ => Err(DecodeError::Mismatch.into()),
         }
     }
 
@@ -235,7 +864,7 @@ impl Harsh {
 
     /// Decodes a hashid into a hex string.
     pub fn decode_hex(&self, value: &str) -> Result<String> {
-        use std::fmt::Write;
+        use core::fmt::Write;
 
         let values = self.decode(value)?;
 
@@ -250,6 +879,187 @@ impl Harsh {
 
         Ok(result)
     }
+
+    /// Encodes a slice of bytes into a hashid.
+    ///
+    /// The bytes are packed big-endian into `u128` values fifteen at a time,
+    /// each below a `1` bit that marks where it starts, so leading zero bytes
+    /// and the exact length survive the round trip. An empty slice is hashed
+    /// as that bit alone. The lottery character marks the hashid as holding
+    /// bytes, so it will not decode with `decode` or `decode_set`.
+    pub fn encode_bytes(&self, bytes: &[u8]) -> String {
+        if bytes.is_empty() {
+            return self.encode_values(&[1u128], Frame::Bytes);
+        }
+
+        let values: Vec<u128> = bytes
+            .chunks(BYTES_PER_VALUE)
+            .map(|chunk| {
+                chunk
+                    .iter()
+                    .fold(1, |value, &byte| value << 8 | u128::from(byte))
+            })
+            .collect();
+
+        self.encode_values(&values, Frame::Bytes)
+    }
+
+    /// Decodes a hashid produced by `encode_bytes` into a vector of bytes.
+    ///
+    /// Hashids produced any other way, including by `encode`, are rejected
+    /// with `Error::Bytes`, as are those whose values do not mark out whole
+    /// bytes or hold fewer than fifteen bytes anywhere but in the last value.
+    pub fn decode_bytes<T: AsRef<str>>(&self, input: T) -> Result<Vec<u8>> {
+        let values = self.decode_values::<u128>(input.as_ref(), Frame::Bytes)?;
+        let last = values.len() - 1;
+
+        let mut bytes = Vec::with_capacity(values.len() * BYTES_PER_VALUE);
+        for (idx, value) in values.into_iter().enumerate() {
+            let bits = (u128::BITS - value.leading_zeros()) as usize;
+            if bits % 8 != 1 {
+                return Err(Error::Bytes);
+            }
+
+            let width = bits / 8;
+            if (idx < last && width != BYTES_PER_VALUE) || (width == 0 && last > 0) {
+                return Err(Error::Bytes);
+            }
+            bytes.extend_from_slice(&value.to_be_bytes()[16 - width..]);
+        }
+
+        Ok(bytes)
+    }
+
+    /// Encodes a UUID into a hashid.
+    ///
+    /// The UUID is hashed as a single 128-bit value, which yields the
+    /// shortest hashid that can represent it.
+    pub fn encode_uuid<U: UuidBytes>(&self, uuid: &U) -> String {
+        self.encode_u128(&[u128::from_be_bytes(uuid.to_uuid_bytes())])
+    }
+
+    /// Decodes a hashid produced by `encode_uuid` into a UUID.
+    ///
+    /// Hashids that do not hold exactly one value are rejected with
+    /// `Error::Uuid`.
+    pub fn decode_uuid<U: UuidBytes, T: AsRef<str>>(&self, input: T) -> Result<U> {
+        match self.decode_u128(input)?[..] {
+            [value] => Ok(U::from_uuid_bytes(value.to_be_bytes())),
+            _ => Err(Error::Uuid),
+        }
+    }
+
+    /// Encodes a value made up of integers, such as a struct that derives
+    /// [`Hashid`](./trait.Hashid.html), into a single hashid.
+    pub fn encode_hashid<H: Hashid>(&self, value: &H) -> String {
+        let mut values = Vec::new();
+        value.push_values(&mut values);
+        self.encode(&values)
+    }
+
+    /// Decodes a hashid produced by `encode_hashid` into a value of type `H`.
+    ///
+    /// Hashids that hold more or fewer values than `H` is made of are
+    /// rejected with `DecodeError::Count`, and those holding a value out of
+    /// range for its field with `DecodeError::Overflow`.
+    pub fn decode_hashid<H: Hashid, T: AsRef<str>>(&self, input: T) -> Result<H> {
+        let values = self.decode(input)?;
+        let mut rest = &values[..];
+        let value = H::read_values(&mut rest)?;
+
+        if rest.is_empty() {
+            Ok(value)
+        } else {
+            Err(DecodeError::Count.into())
+        }
+    }
+
+    /// Encodes a set of `u64` values into a hashid that is usually much
+    /// shorter than the one `encode` would produce for them.
+    ///
+    /// The values are sorted and deduplicated, and each is then hashed as
+    /// its distance from the one before. Sets made mostly of contiguous
+    /// ranges are instead hashed as the start and length of each range,
+    /// whichever takes fewer values. Either way, a leading value records
+    /// which form was used and how long it is, and the lottery character
+    /// marks the hashid as holding a set, so it will not decode with
+    /// `decode` or `decode_bytes`.
+    ///
+    /// Sets of more than 65,536 values are rejected with `Error::Set`, as
+    /// `decode_set` would refuse to expand them. So is any set when fewer
+    /// than three characters of the alphabet are left for values, which
+    /// leaves too few lottery characters to mark it with.
+    pub fn encode_set(&self, values: &[u64]) -> Result<String> {
+        let mut set = values.to_vec();
+        set.sort_unstable();
+        set.dedup();
+
+        if set.len() > MAXIMUM_SET_LENGTH || self.alphabet.len() <= Frame::Set as usize {
+            return Err(Error::Set);
+        }
+
+        let runs = count_runs(&set);
+        let mut encoded = Vec::new();
+        let mut next = 0;
+
+        if runs * 2 < set.len() {
+            encoded.push((runs as u64) << 1 | 1);
+            for run in set.chunk_by(|&a, &b| a + 1 == b) {
+                let (start, end) = (run[0], run[run.len() - 1]);
+                encoded.push(start - next);
+                encoded.push(end - start);
+                next = end.saturating_add(2);
+            }
+        } else {
+            encoded.push((set.len() as u64) << 1);
+            for value in set {
+                encoded.push(value - next);
+                next = value.saturating_add(1);
+            }
+        }
+
+        Ok(self.encode_values(&encoded, Frame::Set))
+    }
+
+    /// Decodes a hashid produced by `encode_set` into the sorted set of
+    /// values it holds.
+    ///
+    /// Hashids produced any other way, including by `encode`, are rejected
+    /// with `Error::Set`, as are those whose values do not describe a set
+    /// and sets that `encode_set` would have hashed in the other form.
+    pub fn decode_set<T: AsRef<str>>(&self, input: T) -> Result<Vec<u64>> {
+        let values = self.decode_values::<u64>(input.as_ref(), Frame::Set)?;
+        let (&header, values) = values.split_first().ok_or(Error::Set)?;
+
+        let (count, is_ranges) = (header >> 1, header & 1 == 1);
+        let width = if is_ranges { 2 } else { 1 };
+        if count > MAXIMUM_SET_LENGTH as u64 || values.len() as u64 != count * width {
+            return Err(Error::Set);
+        }
+
+        let mut set = Vec::new();
+        let mut next = Some(0u64);
+
+        for chunk in values.chunks(width as usize) {
+            let start = next
+                .and_then(|n| n.checked_add(chunk[0]))
+                .ok_or(Error::Set)?;
+            let length = chunk.get(1).copied().unwrap_or(0);
+            if length >= (MAXIMUM_SET_LENGTH - set.len()) as u64 {
+                return Err(Error::Set);
+            }
+
+            let end = start.checked_add(length).ok_or(Error::Set)?;
+            set.extend(start..=end);
+            next = end.checked_add(if is_ranges { 2 } else { 1 });
+        }
+
+        if is_ranges != (count_runs(&set) * 2 < set.len()) {
+            return Err(Error::Set);
+        }
+
+        Ok(set)
+    }
 }
 
 impl Default for Harsh {
@@ -258,41 +1068,173 @@ impl Default for Harsh {
     }
 }
 
+/// An unsigned integer type that may be hashed.
+///
+/// Hashing only ever divides by (or takes the remainder of) small numbers,
+/// so each operation takes a `u64` operand regardless of the value's width.
+trait Value: Clone + PartialEq {
+    const ZERO: Self;
+
+    /// Returns `self % n`.
+    fn rem(&self, n: u64) -> u64;
+
+    /// Returns `self / n`.
+    fn div(self, n: u64) -> Self;
+
+    /// Returns `self * base + digit`, or `None` on overflow.
+    fn push_digit(self, base: u64, digit: u64) -> Option<Self>;
+}
+
+impl Value for u64 {
+    const ZERO: Self = 0;
+
+    #[inline]
+    fn rem(&self, n: u64) -> u64 {
+        self % n
+    }
+
+    #[inline]
+    fn div(self, n: u64) -> Self {
+        self / n
+    }
+
+    #[inline]
+    fn push_digit(self, base: u64, digit: u64) -> Option<Self> {
+        self.checked_mul(base)?.checked_add(digit)
+    }
+}
+
+impl Value for u128 {
+    const ZERO: Self = 0;
+
+    #[inline]
+    fn rem(&self, n: u64) -> u64 {
+        (self % n as u128) as u64
+    }
+
+    #[inline]
+    fn div(self, n: u64) -> Self {
+        self / n as u128
+    }
+
+    #[inline]
+    fn push_digit(self, base: u64, digit: u64) -> Option<Self> {
+        self.checked_mul(base as u128)?.checked_add(digit as u128)
+    }
+}
+
+#[cfg(feature = "num-bigint")]
+impl Value for BigUint {
+    const ZERO: Self = BigUint::ZERO;
+
+    #[inline]
+    fn rem(&self, n: u64) -> u64 {
+        (self % n).iter_u64_digits().next().unwrap_or(0)
+    }
+
+    #[inline]
+    fn div(self, n: u64) -> Self {
+        self / n
+    }
+
+    #[inline]
+    fn push_digit(self, base: u64, digit: u64) -> Option<Self> {
+        Some(self * base + digit)
+    }
+}
+
+/// Counts the contiguous ranges in a sorted, deduplicated set.
+fn count_runs(set: &[u64]) -> usize {
+    set.chunk_by(|&a, &b| a + 1 == b).count()
+}
+
 #[inline]
-fn create_nhash(values: &[u64]) -> u64 {
+fn create_nhash<V: Value>(values: &[V]) -> u64 {
     values
         .iter()
         .enumerate()
-        .fold(0, |a, (idx, value)| a + (value % (idx + 100) as u64))
+        .fold(0, |a, (idx, value)| a + value.rem((idx + 100) as u64))
 }
 
-fn hash(mut value: u64, alphabet: &[u8]) -> String {
+/// Writes `value` in the base of `alphabet`, returning its leading character.
+fn hash<V: Value, S: Sink>(
+    mut value: V,
+    alphabet: &[char],
+    sink: &mut S,
+) -> result::Result<char, fmt::Error> {
     let length = alphabet.len() as u64;
-    let mut hash = Vec::new();
+    let mut hash = ['\0'; MAXIMUM_DIGITS];
+    let mut start = hash.len();
+
+    // Only arbitrary-precision values have more digits than fit in `hash`,
+    // and the leading ones are collected here, last to first.
+    let mut leading = Vec::new();
 
     loop {
-        hash.push(alphabet[(value % length) as usize]);
-        value /= length;
+        let digit = alphabet[value.rem(length) as usize];
+        if start > 0 {
+            start -= 1;
+            hash[start] = digit;
+        } else {
+            leading.push(digit);
+        }
+        value = value.div(length);
 
-        if value == 0 {
-            hash.reverse();
-            return String::from_utf8(hash).expect("omg fml");
+        if value == V::ZERO {
+            break;
         }
     }
+
+    leading.reverse();
+    sink.push_all(&leading)?;
+    sink.push_all(&hash[start..])?;
+    Ok(leading.first().copied().unwrap_or(hash[start]))
+}
+
+/// Fills `buffer` with as much of the lottery character, salt and alphabet,
+/// in that order, as will fit.
+///
+/// Characters salt the shuffle with their whole code point, as hashids.js
+/// does with `codePointAt`, and the salt is read character by character.
+fn fill_salt(buffer: &mut [u32], lottery: char, salt: &str, alphabet: &[char]) {
+    let chars = iter::once(lottery)
+        .chain(salt.chars())
+        .chain(alphabet.iter().copied());
+    for (u, c) in buffer.iter_mut().zip(chars) {
+        *u = c as u32;
+    }
+}
+
+/// Shuffles an alphabet using itself as the salt.
+fn shuffle_alphabet(alphabet: &mut [char]) {
+    let mut salt = [0; MAXIMUM_ALPHABET_LENGTH];
+    let salt = &mut salt[..alphabet.len()];
+    for (u, &c) in salt.iter_mut().zip(alphabet.iter()) {
+        *u = c as u32;
+    }
+    shuffle(alphabet, salt);
 }
 
-fn unhash(input: &[u8], alphabet: &[u8]) -> Option<u64> {
-    input.iter().enumerate().fold(Some(0), |a, (idx, &value)| {
+fn unhash<V: Value>(input: &str, alphabet: &[char], fold: impl Fn(char) -> char) -> Option<V> {
+    input.chars().try_fold(V::ZERO, |a, value| {
+        let value = fold(value);
         let pos = alphabet.iter().position(|&item| item == value)? as u64;
-        let b = (alphabet.len() as u64).checked_pow((input.len() - idx - 1) as u32)?;
-        let c = pos.checked_mul(b)?;
-        a.map(|a| a + c)
+        a.push_digit(alphabet.len() as u64, pos)
     })
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{Harsh, HarshBuilder};
+    use alloc::{string::String, vec, vec::Vec};
+
+    use super::{Error, Frame, Harsh, HarshBuilder};
+    use crate::sink::{Scanner, Sink, Writer};
+
+    fn hash_to_string<V: super::Value>(value: V, alphabet: &[char]) -> String {
+        let mut hash = String::new();
+        super::hash(value, alphabet, &mut Writer(&mut hash)).unwrap();
+        hash
+    }
 
     #[test]
     fn harsh_default_does_not_panic() {
@@ -336,6 +1278,35 @@ mod tests {
         assert_eq!("9LGlaHquq06D", harsh.encode(&[1, 2, 3]));
     }
 
+    #[test]
+    fn can_encode_into() {
+        let harsh = HarshBuilder::new()
+            .salt("this is my salt")
+            .length(12)
+            .build()
+            .expect("failed to initialize harsh");
+
+        let mut hash = String::from("id=");
+        harsh.encode_into(&[1, 2, 3], &mut hash).unwrap();
+        assert_eq!("id=9LGlaHquq06D", hash);
+    }
+
+    #[test]
+    fn can_encode_to_buf() {
+        let harsh = HarshBuilder::new()
+            .salt("this is my salt")
+            .length(12)
+            .build()
+            .expect("failed to initialize harsh");
+
+        let mut buffer = [0; 16];
+        let len = harsh.encode_to_buf(&[1, 2, 3], &mut buffer).unwrap();
+        assert_eq!(b"9LGlaHquq06D", &buffer[..len]);
+
+        let mut buffer = [0; 11];
+        assert!(harsh.encode_to_buf(&[1, 2, 3], &mut buffer).is_err());
+    }
+
     #[test]
     fn can_decode() {
         let harsh = HarshBuilder::new()
@@ -354,6 +1325,39 @@ mod tests {
         );
     }
 
+    #[test]
+    fn can_decode_into() {
+        let harsh = HarshBuilder::new()
+            .salt("this is my salt")
+            .length(12)
+            .build()
+            .expect("failed to initialize harsh");
+
+        let mut values = vec![0];
+        harsh.decode_into("9LGlaHquq06D", &mut values).unwrap();
+        assert_eq!(&[0, 1, 2, 3], &values[..]);
+
+        assert!(harsh.decode_into("9LGlaHquq06E", &mut values).is_err());
+        assert_eq!(&[0, 1, 2, 3], &values[..]);
+    }
+
+    #[test]
+    fn can_decode_to_slice() {
+        let harsh = HarshBuilder::new()
+            .salt("this is my salt")
+            .build()
+            .expect("failed to initialize harsh");
+
+        let mut values = [0; 4];
+        assert_eq!(
+            &[1, 2, 3],
+            harsh.decode_to_slice("laHquq", &mut values).unwrap()
+        );
+
+        let mut values = [0; 2];
+        assert!(harsh.decode_to_slice("laHquq", &mut values).is_err());
+    }
+
     #[test]
     fn can_decode_with_guards() {
         let harsh = HarshBuilder::new()
@@ -603,24 +1607,54 @@ mod tests {
 
     #[test]
     fn create_nhash() {
-        let values = &[1, 2, 3];
+        let values = &[1u64, 2, 3];
         let nhash = super::create_nhash(values);
         assert_eq!(6, nhash);
     }
 
     #[test]
     fn hash() {
-        let result = super::hash(22, b"abcdefghijklmnopqrstuvwxyz");
+        let alphabet = "abcdefghijklmnopqrstuvwxyz".chars().collect::<Vec<_>>();
+        let result = hash_to_string(22u64, &alphabet);
         assert_eq!("w", result);
     }
 
+    #[test]
+    fn unhash() {
+        let alphabet = &"abcdefghijklmnopqrstuvwxyz".chars().collect::<Vec<_>>();
+        let max = hash_to_string(u64::MAX, alphabet);
+
+        assert_eq!(Some(u64::MAX), super::unhash(&max, alphabet, |c| c));
+        assert_eq!(
+            None,
+            super::unhash::<u64>("zzzzzzzzzzzzzzz", alphabet, |c| c)
+        );
+        assert_eq!(
+            Some(26u128.pow(15) - 1),
+            super::unhash("zzzzzzzzzzzzzzz", alphabet, |c| c)
+        );
+    }
+
+    #[cfg(feature = "num-bigint")]
+    #[test]
+    fn hash_beyond_primitive_digits() {
+        use num_bigint::BigUint;
+
+        let alphabet = "01".chars().collect::<Vec<_>>();
+        let value = (BigUint::from(1u8) << 300u32) + 5u8;
+        let result = hash_to_string(value.clone(), &alphabet);
+
+        assert_eq!(value.to_str_radix(2), result);
+        assert_eq!(Some(value), super::unhash(&result, &alphabet, |c| c));
+    }
+
     #[test]
     fn shuffle() {
-        let salt = b"1234";
-        let mut values = "asdfzxcvqwer".bytes().collect::<Vec<_>>();
-        super::shuffle(&mut values, salt);
+        let salt = "1234".chars().map(u32::from).collect::<Vec<_>>();
+        let mut values = "asdfzxcvqwer".chars().collect::<Vec<_>>();
+        super::shuffle(&mut values, &salt);
 
-        assert_eq!("vdwqfrzcsxae", String::from_utf8_lossy(&values));
+        assert_eq!("vdwqfrzcsxae", values.iter().collect::<String>());
     }
 
     #[test]
@@ -632,6 +1666,93 @@ mod tests {
         assert_eq!(vec![1], harsh.decode("ejR").unwrap());
     }
 
+    #[test]
+    fn is_blocked() {
+        let harsh = HarshBuilder::new().blocklist(vec!["word"]).build().unwrap();
+
+        let is_blocked = |hash: &str| {
+            let mut scanner = Scanner::new(&harsh.blocklist);
+            hash.chars().try_for_each(|c| scanner.push(c)).is_err()
+        };
+
+        assert!(is_blocked("aWoRdb"));
+        assert!(is_blocked("xPOOPx"));
+        assert!(!is_blocked("wor"));
+    }
+
+    #[test]
+    fn bytes_must_be_whole() {
+        let harsh = Harsh::default();
+        let rejects = |values: &[u128]| {
+            let hash = harsh.encode_values(values, Frame::Bytes);
+            matches!(harsh.decode_bytes(hash), Err(Error::Bytes))
+        };
+
+        // No bit marking where the bytes start.
+        assert!(rejects(&[0]));
+
+        // A marker that does not leave whole bytes below it.
+        assert!(rejects(&[2]));
+        assert!(rejects(&[1, 2, 3]));
+        assert!(rejects(&[u128::MAX]));
+
+        // Fewer than fifteen bytes before the last value.
+        assert!(rejects(&[1, 0x1ff]));
+        assert!(rejects(&[0x1ff, 0x1ff]));
+
+        let hash = harsh.encode_values(&[0x1ffu128], Frame::Bytes);
+        assert_eq!(vec![0xff], harsh.decode_bytes(hash).unwrap());
+    }
+
+    #[test]
+    fn sets_must_be_canonical() {
+        let harsh = Harsh::default();
+        let decode = |values: &[u64]| harsh.decode_set(harsh.encode_values(values, Frame::Set));
+
+        // {1, 2, 3} as distances, where encode_set would use a range.
+        assert!(matches!(decode(&[6, 1, 0, 0]), Err(Error::Set)));
+        assert_eq!(vec![1, 2, 3], decode(&[3, 1, 2]).unwrap());
+
+        // {1, 5} as ranges, where encode_set would use distances.
+        assert!(matches!(decode(&[5, 1, 0, 2, 0]), Err(Error::Set)));
+        assert_eq!(vec![1, 5], decode(&[4, 1, 3]).unwrap());
+
+        // The empty set only as distances.
+        assert!(matches!(decode(&[1]), Err(Error::Set)));
+        assert_eq!(Vec::<u64>::new(), decode(&[0]).unwrap());
+    }
+
+    #[test]
+    fn sets_must_fit() {
+        let harsh = Harsh::default();
+        let decode = |values: &[u64]| harsh.decode_set(harsh.encode_values(values, Frame::Set));
+
+        // Values past u64::MAX.
+        assert!(matches!(decode(&[4, u64::MAX, 0]), Err(Error::Set)));
+        assert!(matches!(decode(&[3, u64::MAX - 1, 5]), Err(Error::Set)));
+
+        // More values than decode_set will expand.
+        assert!(matches!(decode(&[3, 0, u64::MAX]), Err(Error::Set)));
+    }
+
+    #[test]
+    fn frames_are_told_apart() {
+        let harsh = HarshBuilder::new()
+            .alphabet("abcdefghijklmnopqrs")
+            .build()
+            .unwrap();
+
+        for value in 0..1000u64 {
+            let plain = harsh.encode(&[value]);
+            let bytes = harsh.encode_values(&[value], Frame::Bytes);
+            let set = harsh.encode_values(&[value], Frame::Set);
+
+            assert_ne!(plain, bytes);
+            assert_ne!(plain, set);
+            assert_ne!(bytes, set);
+        }
+    }
+
     #[test]
     #[should_panic]
     fn appended_garbage_data_invalidates_hashid() {
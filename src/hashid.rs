@@ -0,0 +1,153 @@
+use core::{
+    cmp::Ordering,
+    fmt,
+    hash::{Hash, Hasher},
+    marker::PhantomData,
+    str::FromStr,
+};
+
+use crate::harsh::{DecodeError, Error, Harsh};
+
+/// A kind of entity identified by a [`HashId`](struct.HashId.html).
+///
+/// Each kind declares a salt of its own, and optionally a prefix, which
+/// [`HarshBuilder::for_kind`](struct.HarshBuilder.html#method.for_kind)
+/// joins to a base configuration shared by every kind. Because the salts
+/// differ, the id of one kind of entity will not decode as an id of another.
+///
+/// ```rust
+/// use harsh::{Harsh, HarshBuilder, HashId, HashIdKind};
+/// use std::sync::OnceLock;
+///
+/// fn base() -> HarshBuilder {
+///     // Usually loaded from configuration at runtime.
+///     Harsh::builder().salt("this is my salt").length(8)
+/// }
+///
+/// struct User;
+///
+/// impl HashIdKind for User {
+///     const SALT: &'static str = ":user";
+///
+///     fn harsh() -> &'static Harsh {
+///         static HARSH: OnceLock<Harsh> = OnceLock::new();
+///         HARSH.get_or_init(|| base().for_kind::<Self>().build().unwrap())
+///     }
+/// }
+///
+/// struct Order;
+///
+/// impl HashIdKind for Order {
+///     const SALT: &'static str = ":order";
+///     const PREFIX: &'static str = "ord";
+///
+///     fn harsh() -> &'static Harsh {
+///         static HARSH: OnceLock<Harsh> = OnceLock::new();
+///         HARSH.get_or_init(|| base().for_kind::<Self>().build().unwrap())
+///     }
+/// }
+///
+/// let id = HashId::<User>::new(42).to_string();
+/// assert_eq!(42, id.parse::<HashId<User>>().unwrap().get());
+/// assert!(id.parse::<HashId<Order>>().is_err());
+/// ```
+pub trait HashIdKind {
+    /// The salt joined to the end of the base salt for ids of this kind.
+    const SALT: &'static str;
+
+    /// The prefix of ids of this kind, or an empty string to keep the prefix
+    /// of the base configuration.
+    const PREFIX: &'static str = "";
+
+    /// Returns the `Harsh` used to encode and decode ids of this kind.
+    fn harsh() -> &'static Harsh;
+}
+
+/// An id for an entity of kind `T`, displayed and parsed as a hashid.
+///
+/// Ids are compared, ordered and hashed by their numeric value.
+pub struct HashId<T> {
+    value: u64,
+    kind: PhantomData<fn() -> T>,
+}
+
+impl<T> HashId<T> {
+    /// Creates an id from its numeric value.
+    pub const fn new(value: u64) -> Self {
+        HashId {
+            value,
+            kind: PhantomData,
+        }
+    }
+
+    /// Returns the id's numeric value.
+    pub const fn get(self) -> u64 {
+        self.value
+    }
+}
+
+impl<T: HashIdKind> fmt::Display for HashId<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        T::harsh().encode_into(&[self.value], f)
+    }
+}
+
+impl<T: HashIdKind> FromStr for HashId<T> {
+    type Err = Error;
+
+    /// Parses a hashid holding exactly one value.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut values = [0; 2];
+        match T::harsh().decode_to_slice(s, &mut values) {
+            Ok(&[value]) => Ok(HashId::new(value)),
+            Ok(_) | Err(Error::Buffer) => Err(DecodeError::Count.into()),
+            Err(e) => Err(e),
+        }
+    }
+}
+
+impl<T> From<HashId<T>> for u64 {
+    fn from(id: HashId<T>) -> u64 {
+        id.value
+    }
+}
+
+impl<T> fmt::Debug for HashId<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("HashId").field(&self.value).finish()
+    }
+}
+
+impl<T> Clone for HashId<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for HashId<T> {}
+
+impl<T> PartialEq for HashId<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value
+    }
+}
+
+impl<T> Eq for HashId<T> {}
+
+impl<T> PartialOrd for HashId<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T> Ord for HashId<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.value.cmp(&other.value)
+    }
+}
+
+impl<T> Hash for HashId<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.value.hash(state);
+    }
+}
@@ -0,0 +1,67 @@
+use core::convert::TryFrom;
+
+/// A primitive integer type that may be hashed.
+///
+/// Every primitive integer implements this, so that any of them may be passed
+/// to [`Harsh::encode_integers`](./struct.Harsh.html#method.encode_integers)
+/// and returned from
+/// [`Harsh::decode_integers`](./struct.Harsh.html#method.decode_integers).
+///
+/// Unsigned integers are hashed as they are. Signed integers are first
+/// zigzag encoded, which interleaves negative and positive numbers so that
+/// small magnitudes stay small and no two values share a hashid:
+///
+/// | value | hashed as |
+/// |-------|-----------|
+/// | 0     | 0         |
+/// | -1    | 1         |
+/// | 1     | 2         |
+/// | -2    | 3         |
+/// | 2     | 4         |
+///
+/// This means that a non-negative signed value does not produce the same
+/// hashid as the unsigned value it would be cast to.
+pub trait Integer: Copy {
+    /// Returns the unsigned value that `self` is hashed as.
+    fn to_unsigned(self) -> u128;
+
+    /// Returns the integer that is hashed as `value`, or `None` if there is
+    /// none of this type.
+    fn from_unsigned(value: u128) -> Option<Self>;
+}
+
+macro_rules! unsigned {
+    ($($t:ty)*) => {$(
+        impl Integer for $t {
+            #[inline]
+            fn to_unsigned(self) -> u128 {
+                self as u128
+            }
+
+            #[inline]
+            fn from_unsigned(value: u128) -> Option<Self> {
+                <$t>::try_from(value).ok()
+            }
+        }
+    )*};
+}
+
+macro_rules! signed {
+    ($($t:ty => $u:ty)*) => {$(
+        impl Integer for $t {
+            #[inline]
+            fn to_unsigned(self) -> u128 {
+                ((self << 1) ^ (self >> (<$t>::BITS - 1))) as $u as u128
+            }
+
+            #[inline]
+            fn from_unsigned(value: u128) -> Option<Self> {
+                let value = <$u>::try_from(value).ok()?;
+                Some((value >> 1) as $t ^ -((value & 1) as $t))
+            }
+        }
+    )*};
+}
+
+unsigned!(u8 u16 u32 u64 u128 usize);
+signed!(i8 => u8 i16 => u16 i32 => u32 i64 => u64 i128 => u128 isize => usize);
@@ -0,0 +1,635 @@
+use crate::{
+    builder::{
+        BuildError, DEFAULT_ALPHABET, DEFAULT_PREFIX_SEPARATOR, DEFAULT_SEPARATORS, MAXIMUM_LENGTH,
+    },
+    preset::Preset,
+    shuffle,
+};
+
+const MINIMUM_ALPHABET_LENGTH: usize = 16;
+const MAXIMUM_ALPHABET_LENGTH: usize = 256;
+const SEPARATOR_DIV: usize = 7;
+const GUARD_DIV: usize = 12;
+
+/// The characters of an alphabet, divided into those used for values,
+/// separators and guards, along with the characters read as them when
+/// decoding.
+///
+/// This is worked out entirely in a `const fn` so that the `harsh!` macro can
+/// do it at compile time; `HarshBuilder` does exactly the same at runtime.
+#[doc(hidden)]
+#[derive(Clone, Copy, Debug)]
+pub struct Layout {
+    chars: [char; MAXIMUM_ALPHABET_LENGTH],
+    alphabet: usize,
+    separators: usize,
+    guards: usize,
+    case_insensitive: bool,
+    fold: [u8; 128],
+    folds: bool,
+}
+
+impl Layout {
+    /// Lays out an alphabet, whose ASCII letters are first brought into
+    /// lowercase if `case_insensitive` is set.
+    ///
+    /// The alphabet and separators are UTF-8, and may hold at most 256
+    /// distinct characters. Characters that can't be seen are rejected, and
+    /// characters repeated in the alphabet are kept only the first time.
+    pub(crate) const fn new(
+        alphabet: &[u8],
+        separators: &[u8],
+        salt: &str,
+        case_insensitive: bool,
+    ) -> Result<Layout, BuildError> {
+        if core::str::from_utf8(alphabet).is_err() || core::str::from_utf8(separators).is_err() {
+            return Err(BuildError::IllegalCharacter(char::REPLACEMENT_CHARACTER));
+        }
+
+        let mut unique = ['\0'; MAXIMUM_ALPHABET_LENGTH];
+        let mut unique_len = 0;
+
+        let mut idx = 0;
+        while idx < alphabet.len() {
+            let (c, width) = next_char(alphabet, idx);
+            if !is_visible(c) {
+                return Err(BuildError::IllegalCharacter(c));
+            }
+
+            let c = fold_case(c, case_insensitive);
+
+            if !contains(unique.split_at(unique_len).0, c) {
+                if unique_len == MAXIMUM_ALPHABET_LENGTH {
+                    return Err(BuildError::AlphabetTooLong);
+                }
+                unique[unique_len] = c;
+                unique_len += 1;
+            }
+            idx += width;
+        }
+
+        if unique_len < MINIMUM_ALPHABET_LENGTH {
+            return Err(BuildError::AlphabetLength);
+        }
+
+        let (salt, salt_len) = widen_salt(salt);
+        let salt = salt.split_at(salt_len).0;
+
+        // Separators that don't appear in the alphabet are dropped, and the
+        // rest are taken out of it.
+        let unique = unique.split_at(unique_len).0;
+        let mut seps = ['\0'; MAXIMUM_ALPHABET_LENGTH];
+        let mut seps_len = 0;
+
+        let mut idx = 0;
+        while idx < separators.len() {
+            let (c, width) = next_char(separators, idx);
+            let c = fold_case(c, case_insensitive);
+            if contains(unique, c) && !contains(seps.split_at(seps_len).0, c) {
+                seps[seps_len] = c;
+                seps_len += 1;
+            }
+            idx += width;
+        }
+
+        let mut alpha = ['\0'; MAXIMUM_ALPHABET_LENGTH];
+        let mut alpha_len = 0;
+
+        let mut idx = 0;
+        while idx < unique.len() {
+            if !contains(seps.split_at(seps_len).0, unique[idx]) {
+                alpha[alpha_len] = unique[idx];
+                alpha_len += 1;
+            }
+            idx += 1;
+        }
+
+        shuffle(seps.split_at_mut(seps_len).0, salt);
+
+        // There should be one separator for every 3.5 characters of alphabet,
+        // which is kept in integers as two separators for every seven.
+        if seps_len == 0 || alpha_len * 2 > seps_len * SEPARATOR_DIV {
+            let length = match (alpha_len * 2).div_ceil(SEPARATOR_DIV) {
+                1 => 2,
+                n => n,
+            };
+
+            if length > seps_len {
+                let diff = length - seps_len;
+
+                let mut idx = 0;
+                while idx < diff {
+                    seps[seps_len + idx] = alpha[idx];
+                    idx += 1;
+                }
+
+                let mut idx = diff;
+                while idx < alpha_len {
+                    alpha[idx - diff] = alpha[idx];
+                    idx += 1;
+                }
+
+                alpha_len -= diff;
+            }
+
+            seps_len = length;
+        }
+
+        // Values are written in the base of what's left of the alphabet, so
+        // it needs at least two characters.
+        if alpha_len < 2 {
+            return Err(BuildError::AlphabetLength);
+        }
+
+        shuffle(alpha.split_at_mut(alpha_len).0, salt);
+
+        // Guards come from the front of the alphabet, unless the alphabet is
+        // too small to spare them, in which case they come from the separators.
+        let guards_len = alpha_len.div_ceil(GUARD_DIV);
+        let alpha = alpha.split_at(alpha_len).0;
+        let seps = seps.split_at(seps_len).0;
+        let (alpha, seps, guards) = if alpha_len < 3 {
+            let (guards, seps) = seps.split_at(guards_len);
+            (alpha, seps, guards)
+        } else {
+            let (guards, alpha) = alpha.split_at(guards_len);
+            (alpha, seps, guards)
+        };
+
+        let mut layout = Layout {
+            chars: ['\0'; MAXIMUM_ALPHABET_LENGTH],
+            alphabet: alpha.len(),
+            separators: 0,
+            guards: guards.len(),
+            case_insensitive,
+            fold: [0; 128],
+            folds: case_insensitive,
+        };
+
+        let mut idx = 0;
+        while idx < layout.fold.len() {
+            layout.fold[idx] = fold_case(idx as u8 as char, case_insensitive) as u8;
+            idx += 1;
+        }
+
+        let mut len = 0;
+        let mut idx = 0;
+        while idx < alpha.len() {
+            layout.chars[len] = alpha[idx];
+            len += 1;
+            idx += 1;
+        }
+
+        idx = 0;
+        while idx < seps.len() {
+            layout.chars[len] = seps[idx];
+            len += 1;
+            idx += 1;
+        }
+
+        idx = 0;
+        while idx < guards.len() {
+            layout.chars[len] = guards[idx];
+            len += 1;
+            idx += 1;
+        }
+
+        layout.separators = len - layout.alphabet - layout.guards;
+        Ok(layout)
+    }
+
+    /// Reads the lookalikes of the preset as the characters they resemble,
+    /// unless they are in the alphabet themselves.
+    pub(crate) const fn with_aliases(mut self, preset: Preset) -> Layout {
+        let mut idx = 0;
+        while idx < self.fold.len() {
+            let alias = preset.alias(idx as u8);
+            let characters = self.characters();
+            if !contains(characters, idx as u8 as char) && contains(characters, alias as char) {
+                self.fold[idx] = alias;
+                self.folds = true;
+            }
+            idx += 1;
+        }
+
+        self
+    }
+
+    /// Checks that every character of the alphabet is ASCII, and so takes up
+    /// a single byte.
+    pub(crate) const fn check_ascii(&self) -> Result<(), BuildError> {
+        let characters = self.characters();
+        let mut idx = 0;
+        while idx < characters.len() {
+            if !characters[idx].is_ascii() {
+                return Err(BuildError::IllegalCharacter(characters[idx]));
+            }
+            idx += 1;
+        }
+
+        Ok(())
+    }
+
+    /// Checks that at least one of a set of separators is in the alphabet,
+    /// for when they were given explicitly rather than left as the defaults.
+    pub(crate) const fn check_separators(&self, separators: &[u8]) -> Result<(), BuildError> {
+        if separators.is_empty() {
+            return Ok(());
+        }
+
+        let characters = self.characters();
+        let mut idx = 0;
+        while idx < separators.len() {
+            let (c, width) = next_char(separators, idx);
+            if contains(characters, fold_case(c, self.case_insensitive)) {
+                return Ok(());
+            }
+            idx += width;
+        }
+
+        Err(BuildError::Separator)
+    }
+
+    /// Checks that a prefix and its separator can be told apart from the
+    /// hashids that follow them.
+    ///
+    /// An empty prefix is never written, so its separator goes unchecked.
+    pub(crate) const fn check_prefix(
+        &self,
+        prefix: &str,
+        separator: char,
+    ) -> Result<(), BuildError> {
+        if prefix.is_empty() {
+            return Ok(());
+        }
+
+        let characters = self.characters();
+        if !separator.is_ascii_graphic() {
+            return Err(BuildError::PrefixSeparator(separator));
+        }
+
+        if contains(characters, fold_case(separator, self.case_insensitive)) {
+            return Err(BuildError::PrefixSeparator(separator));
+        }
+
+        let bytes = prefix.as_bytes();
+        let mut idx = 0;
+        while idx < bytes.len() {
+            let (c, width) = next_char(bytes, idx);
+            if !c.is_ascii_graphic() || c == separator {
+                return Err(BuildError::Prefix(c));
+            }
+            idx += width;
+        }
+
+        Ok(())
+    }
+
+    /// Lays out a `StaticConfig`, failing the build if it is invalid.
+    pub const fn from_config(config: &StaticConfig) -> Layout {
+        let (alphabet, separators) = match config.preset {
+            Some(preset) => (preset.alphabet(), preset.separators()),
+            None => (DEFAULT_ALPHABET, DEFAULT_SEPARATORS),
+        };
+        let alphabet = match config.alphabet {
+            Some(alphabet) => alphabet,
+            None => alphabet,
+        };
+        let separators = match config.separators {
+            Some(separators) => separators,
+            None => separators,
+        };
+
+        let layout = match Layout::new(
+            alphabet.as_bytes(),
+            separators.as_bytes(),
+            config.salt,
+            config.case_insensitive,
+        ) {
+            Ok(layout) => match config.preset {
+                Some(preset) => layout.with_aliases(preset),
+                None => layout,
+            },
+            Err(BuildError::AlphabetLength) => {
+                panic!("alphabet does not include enough distinct characters")
+            }
+            Err(BuildError::AlphabetTooLong) => {
+                panic!("alphabet includes more than 256 distinct characters")
+            }
+            Err(_) => panic!("alphabet contains an illegal character"),
+        };
+
+        if config.ascii_only && layout.check_ascii().is_err() {
+            panic!("alphabet contains a character that is not ASCII");
+        }
+
+        if let Some(separators) = config.separators {
+            if layout.check_separators(separators.as_bytes()).is_err() {
+                panic!("separators contain no character found in the alphabet");
+            }
+        }
+
+        if config.length > MAXIMUM_LENGTH {
+            panic!("minimum length is too large");
+        }
+
+        match layout.check_prefix(config.prefix, config.prefix_separator) {
+            Ok(()) => layout,
+            Err(BuildError::PrefixSeparator(_)) => {
+                panic!("prefix separator is illegal or found in the alphabet")
+            }
+            Err(_) => panic!("prefix contains an illegal character"),
+        }
+    }
+
+    /// Every distinct character in the alphabet.
+    pub(crate) const fn characters(&self) -> &[char] {
+        self.chars
+            .split_at(self.alphabet + self.separators + self.guards)
+            .0
+    }
+
+    pub(crate) const fn alphabet(&self) -> &[char] {
+        self.chars.split_at(self.alphabet).0
+    }
+
+    pub(crate) const fn separators(&self) -> &[char] {
+        self.chars
+            .split_at(self.alphabet)
+            .1
+            .split_at(self.separators)
+            .0
+    }
+
+    pub(crate) const fn guards(&self) -> &[char] {
+        self.chars
+            .split_at(self.alphabet + self.separators)
+            .1
+            .split_at(self.guards)
+            .0
+    }
+
+    pub(crate) const fn is_case_insensitive(&self) -> bool {
+        self.case_insensitive
+    }
+
+    /// The character each ASCII character is read as when decoding, or
+    /// nothing if every character is read as itself.
+    pub(crate) const fn fold(&self) -> &[u8] {
+        if self.folds {
+            &self.fold
+        } else {
+            &[]
+        }
+    }
+}
+
+const fn fold_case(c: char, case_insensitive: bool) -> char {
+    if case_insensitive {
+        c.to_ascii_lowercase()
+    } else {
+        c
+    }
+}
+
+/// Returns whether `c` can be seen, which rules out control characters,
+/// whitespace and invisible formatting characters.
+const fn is_visible(c: char) -> bool {
+    !matches!(
+        c,
+        '\0'..='\u{20}'
+            | '\u{7f}'..='\u{a0}'
+            | '\u{ad}'
+            | '\u{1680}'
+            | '\u{180e}'
+            | '\u{2000}'..='\u{200f}'
+            | '\u{2028}'..='\u{202f}'
+            | '\u{205f}'..='\u{206f}'
+            | '\u{3000}'
+            | '\u{feff}'
+    )
+}
+
+const fn contains(chars: &[char], c: char) -> bool {
+    let mut idx = 0;
+    while idx < chars.len() {
+        if chars[idx] == c {
+            return true;
+        }
+        idx += 1;
+    }
+    false
+}
+
+/// Widens as much of a salt as a shuffle can read into the code points it is
+/// shuffled with, character by character as hashids.js reads it. A shuffle of
+/// `n` characters reads at most `n - 1` of them, so cutting the salt to the
+/// largest alphabet changes nothing.
+const fn widen_salt(salt: &str) -> ([u32; MAXIMUM_ALPHABET_LENGTH], usize) {
+    let bytes = salt.as_bytes();
+    let mut wide = [0; MAXIMUM_ALPHABET_LENGTH];
+    let mut len = 0;
+
+    let mut idx = 0;
+    while idx < bytes.len() && len < MAXIMUM_ALPHABET_LENGTH {
+        let (c, width) = next_char(bytes, idx);
+        wide[len] = c as u32;
+        len += 1;
+        idx += width;
+    }
+    (wide, len)
+}
+
+/// Decodes the character of some valid UTF-8 starting at `idx`, returning it
+/// along with its width, since `str::chars` is not available in a `const fn`.
+const fn next_char(bytes: &[u8], idx: usize) -> (char, usize) {
+    let (width, mut code) = match bytes[idx] {
+        u if u < 0x80 => return (u as char, 1),
+        u if u < 0xe0 => (2, u as u32 & 0x1f),
+        u if u < 0xf0 => (3, u as u32 & 0x0f),
+        u => (4, u as u32 & 0x07),
+    };
+
+    let mut offset = 1;
+    while offset < width {
+        code = (code << 6) | (bytes[idx + offset] as u32 & 0x3f);
+        offset += 1;
+    }
+
+    match char::from_u32(code) {
+        Some(c) => (c, width),
+        None => (char::REPLACEMENT_CHARACTER, width),
+    }
+}
+
+/// The settings accepted by the `harsh!` macro.
+#[doc(hidden)]
+pub struct StaticConfig {
+    pub salt: &'static str,
+    pub alphabet: Option<&'static str>,
+    pub separators: Option<&'static str>,
+    pub preset: Option<Preset>,
+    pub length: usize,
+    pub prefix: &'static str,
+    pub prefix_separator: char,
+    pub checksum: bool,
+    pub case_insensitive: bool,
+    pub ascii_only: bool,
+}
+
+impl StaticConfig {
+    pub const DEFAULT: StaticConfig = StaticConfig {
+        salt: "",
+        alphabet: None,
+        separators: None,
+        preset: None,
+        length: 0,
+        prefix: "",
+        prefix_separator: DEFAULT_PREFIX_SEPARATOR,
+        checksum: false,
+        case_insensitive: false,
+        ascii_only: false,
+    };
+}
+
+/// Returns the combined length of the parts of a salt.
+#[doc(hidden)]
+pub const fn salt_len(parts: &[&str]) -> usize {
+    let mut len = 0;
+    let mut idx = 0;
+    while idx < parts.len() {
+        len += parts[idx].len();
+        idx += 1;
+    }
+    len
+}
+
+/// Joins the parts of a salt, which must be exactly `N` bytes long.
+#[doc(hidden)]
+pub const fn join_salt<const N: usize>(parts: &[&str]) -> [u8; N] {
+    let mut salt = [0; N];
+    let mut len = 0;
+    let mut idx = 0;
+    while idx < parts.len() {
+        let part = parts[idx].as_bytes();
+        let mut offset = 0;
+        while offset < part.len() {
+            salt[len] = part[offset];
+            len += 1;
+            offset += 1;
+        }
+        idx += 1;
+    }
+    salt
+}
+
+/// Views a salt joined by `join_salt` as a string again.
+#[doc(hidden)]
+pub const fn salt_str(salt: &[u8]) -> &str {
+    match core::str::from_utf8(salt) {
+        Ok(salt) => salt,
+        Err(_) => panic!("salt is not valid UTF-8"),
+    }
+}
+
+/// Creates a `Harsh` whose alphabet, separators and guards are
+/// worked out at compile time.
+///
+/// Takes the same settings as `HarshBuilder` (`salt`, `alphabet`,
+/// `separators`, `preset`, `length`, `prefix`, `prefix_separator`,
+/// `checksum`, `case_insensitive` and `ascii_only`, but not the blocklist) as
+/// `name = value` pairs. Each value must be a constant expression, and an
+/// invalid configuration fails the build rather than returning an error.
+///
+/// ```rust
+/// use harsh::{harsh, Harsh};
+///
+/// const HARSH: Harsh = harsh!(salt = "this is my salt", length = 8);
+///
+/// assert_eq!("GlaHquq0", HARSH.encode(&[1, 2, 3]));
+/// ```
+///
+/// The salt may also be given as a list of strings, which are joined. This
+/// lets several instances share a base salt while each adding their own:
+///
+/// ```rust
+/// # use harsh::{harsh, Harsh};
+/// const SALT: &str = "this is my salt";
+///
+/// const USERS: Harsh = harsh!(salt = [SALT, ":user"]);
+/// const ORDERS: Harsh = harsh!(salt = [SALT, ":order"]);
+///
+/// assert_ne!(USERS.encode(&[1]), ORDERS.encode(&[1]));
+/// ```
+///
+/// ```compile_fail
+/// const HARSH: harsh::Harsh = harsh::harsh!(alphabet = "abc");
+/// ```
+#[macro_export]
+macro_rules! harsh {
+    ($($config:tt)*) => {
+        $crate::__harsh_config!([] $($config)*)
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __harsh_config {
+    ([$($fields:tt)*] $(,)?) => {{
+        const __HARSH_CONFIG: $crate::__private::StaticConfig = $crate::__private::StaticConfig {
+            $($fields)*
+            ..$crate::__private::StaticConfig::DEFAULT
+        };
+        const __HARSH_LAYOUT: $crate::__private::Layout =
+            $crate::__private::Layout::from_config(&__HARSH_CONFIG);
+
+        $crate::Harsh::from_layout(
+            &__HARSH_LAYOUT,
+            __HARSH_CONFIG.salt,
+            __HARSH_CONFIG.length,
+            __HARSH_CONFIG.prefix,
+            __HARSH_CONFIG.prefix_separator,
+            __HARSH_CONFIG.checksum,
+        )
+    }};
+
+    ([$($fields:tt)*] salt = [$($part:expr),* $(,)?] $(, $($rest:tt)*)?) => {
+        $crate::__harsh_config!(
+            [
+                $($fields)*
+                salt: {
+                    const __HARSH_SALT_PARTS: &[&str] = &[$($part),*];
+                    const __HARSH_SALT: [u8; $crate::__private::salt_len(__HARSH_SALT_PARTS)] =
+                        $crate::__private::join_salt(__HARSH_SALT_PARTS);
+                    $crate::__private::salt_str(&__HARSH_SALT)
+                },
+            ]
+            $($($rest)*)?
+        )
+    };
+
+    ([$($fields:tt)*] alphabet = $value:expr $(, $($rest:tt)*)?) => {
+        $crate::__harsh_config!(
+            [$($fields)* alphabet: ::core::option::Option::Some($value),]
+            $($($rest)*)?
+        )
+    };
+
+    ([$($fields:tt)*] separators = $value:expr $(, $($rest:tt)*)?) => {
+        $crate::__harsh_config!(
+            [$($fields)* separators: ::core::option::Option::Some($value),]
+            $($($rest)*)?
+        )
+    };
+
+    ([$($fields:tt)*] preset = $value:expr $(, $($rest:tt)*)?) => {
+        $crate::__harsh_config!(
+            [$($fields)* preset: ::core::option::Option::Some($value),]
+            $($($rest)*)?
+        )
+    };
+
+    ([$($fields:tt)*] $name:ident = $value:expr $(, $($rest:tt)*)?) => {
+        $crate::__harsh_config!([$($fields)* $name: $value,] $($($rest)*)?)
+    };
+}
@@ -31,14 +31,10 @@
 //!
 //! ```rust
 //! # use harsh::Harsh;
-//! # use std::error::Error;
-//! # fn main() -> Result<(), Box<dyn Error>> {
-//! let harsh = Harsh::builder().salt("salt goes here!").build()?;
+//! let harsh = Harsh::builder().salt("salt goes here!").build().unwrap();
 //! let encoded = harsh.encode(&[1, 2, 3, 4, 5]);
 //!
 //! assert_eq!("xrUQTnhgu7", encoded);
-//! # Ok(())
-//! # }
 //! ```
 //!
 //! ## Decoding
@@ -49,36 +45,95 @@
 //!
 //! ```rust
 //! # use harsh::Harsh;
-//! # use std::error::Error;
-//! # fn main() -> Result<(), Box<dyn Error>> {
-//! let harsh = Harsh::builder().salt("salt goes here!").build()?;
+//! let harsh = Harsh::builder().salt("salt goes here!").build().unwrap();
 //! # let encoded = harsh.encode(&[1, 2, 3, 4, 5]);
-//! let decoded = harsh.decode(&encoded)?;
+//! let decoded = harsh.decode(&encoded).unwrap();
 //!
 //! assert_eq!(&decoded, &[1, 2, 3, 4, 5]);
-//! # Ok(())
-//! # }
 //! ```
+//!
+//! ## Sqids
+//!
+//! [`Sqids`](./struct.Sqids.html) implements the successor to the Hashids
+//! scheme and is configured in the same way, through
+//! [`SqidsBuilder`](./struct.SqidsBuilder.html). Its ids match those produced
+//! by the other Sqids libraries.
+//!
+//! ```rust
+//! # use harsh::Sqids;
+//! let sqids = Sqids::default();
+//! let encoded = sqids.encode(&[1, 2, 3]).unwrap();
+//!
+//! assert_eq!("86Rf07", encoded);
+//! assert_eq!(&sqids.decode(&encoded).unwrap(), &[1, 2, 3]);
+//! ```
+//!
+//! ## `no_std`
+//!
+//! Harsh only needs `alloc`. Disable the default `std` feature to use it
+//! without the standard library, which leaves out the `std::error::Error`
+//! impls and the thread-local serde helpers.
 
+#![no_std]
+
+extern crate alloc;
+
+#[cfg(feature = "std")]
+extern crate std;
+
+mod blocklist;
 mod builder;
+mod fields;
 mod harsh;
+mod hashid;
+mod integer;
+mod layout;
+mod preset;
+mod ring;
+mod sink;
+mod sqids;
+mod uuid;
+
+#[cfg(feature = "serde")]
+pub mod serde;
+
+#[doc(hidden)]
+pub mod __private {
+    pub use alloc::vec::Vec;
+
+    pub use crate::layout::{join_salt, salt_len, salt_str, Layout, StaticConfig};
+}
 
 pub use crate::{
-    builder::{BuildError, HarshBuilder},
-    harsh::{Error, Harsh},
+    builder::{BuildError, BuildWarning, HarshBuilder, HarshConfig},
+    fields::Hashid,
+    harsh::{DecodeError, Error, Harsh},
+    hashid::{HashId, HashIdKind},
+    integer::Integer,
+    preset::Preset,
+    ring::{HarshRing, RingMatch},
+    sqids::{Sqids, SqidsBuilder},
+    uuid::UuidBytes,
 };
 
-fn shuffle(values: &mut [u8], salt: &[u8]) {
+#[cfg(feature = "derive")]
+pub use harsh_derive::Hashid;
+
+/// Shuffles `values` as hashids.js does, salted by code points.
+const fn shuffle(values: &mut [char], salt: &[u32]) {
     if salt.is_empty() {
         return;
     }
 
-    let values_length = values.len();
     let salt_length = salt.len();
     let (mut v, mut p) = (0, 0);
+    let mut i = values.len();
 
-    for i in (1..values_length).map(|i| values_length - i) {
-        v %= salt_length;
+    while i > 1 {
+        i -= 1;
+        if v == salt_length {
+            v = 0;
+        }
 
         let n = salt[v] as usize;
         p += n;
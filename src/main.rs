@@ -0,0 +1,283 @@
+//! Command-line interface for encoding and decoding hashids.
+//!
+//! Values are taken from the command line or, when none are given, read from
+//! standard input one line at a time. The exit status is `0` on success, `2`
+//! on a usage error, `3` when the hasher cannot be configured (a
+//! `BuildError`), `4` when any input fails to encode or decode, and `1` when
+//! standard input or output fails.
+
+use std::{
+    fmt::Display,
+    io::{self, BufRead, Write},
+    process,
+};
+
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use harsh::{Harsh, HarshBuilder, Preset};
+use serde_json::json;
+
+const EXIT_IO: i32 = 1;
+const EXIT_BUILD: i32 = 3;
+const EXIT_DECODE: i32 = 4;
+
+#[derive(Debug, Parser)]
+#[command(name = "harsh", version, about = "Encode and decode hashids")]
+struct Opts {
+    #[command(flatten)]
+    config: Config,
+
+    /// Write one JSON object per result instead of plain text
+    #[arg(long, global = true)]
+    json: bool,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Args)]
+struct Config {
+    /// Salt used to make hashids unique
+    #[arg(short, long, global = true)]
+    salt: Option<String>,
+
+    /// Alphabet of at least 16 unique characters
+    #[arg(short, long, global = true)]
+    alphabet: Option<String>,
+
+    /// Characters used to separate values
+    #[arg(long, global = true)]
+    separators: Option<String>,
+
+    /// Ready-made alphabet and separators
+    #[arg(long, global = true, value_enum)]
+    preset: Option<PresetArg>,
+
+    /// Minimum hashid length
+    #[arg(short, long, global = true, default_value_t = 0)]
+    length: usize,
+
+    /// Prefix written ahead of every hashid
+    #[arg(short, long, global = true)]
+    prefix: Option<String>,
+
+    /// Character joining the prefix to the hashid
+    #[arg(long, global = true)]
+    prefix_separator: Option<char>,
+
+    /// Append a check character to catch mistyped hashids
+    #[arg(long, global = true)]
+    checksum: bool,
+
+    /// Encode ASCII letters in lowercase and decode them in any case
+    #[arg(long, global = true)]
+    case_insensitive: bool,
+
+    /// Reject alphabets with characters that are not ASCII
+    #[arg(long, global = true)]
+    ascii_only: bool,
+}
+
+#[derive(Clone, Copy, Debug, ValueEnum)]
+enum PresetArg {
+    Crockford,
+    Base58,
+    Lowercase,
+    Unambiguous,
+}
+
+impl From<PresetArg> for Preset {
+    fn from(preset: PresetArg) -> Preset {
+        match preset {
+            PresetArg::Crockford => Preset::Crockford,
+            PresetArg::Base58 => Preset::Base58,
+            PresetArg::Lowercase => Preset::Lowercase,
+            PresetArg::Unambiguous => Preset::Unambiguous,
+        }
+    }
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Encode numbers into a single hashid; each line of input is one hashid
+    Encode { values: Vec<String> },
+
+    /// Decode each hashid into its numbers
+    Decode { hashids: Vec<String> },
+
+    /// Encode each hex string into a hashid
+    EncodeHex { values: Vec<String> },
+
+    /// Decode each hashid into a hex string
+    DecodeHex { hashids: Vec<String> },
+}
+
+impl Config {
+    fn build(self) -> Result<Harsh, harsh::BuildError> {
+        let mut builder = HarshBuilder::new()
+            .length(self.length)
+            .checksum(self.checksum)
+            .case_insensitive(self.case_insensitive)
+            .ascii_only(self.ascii_only);
+
+        if let Some(salt) = self.salt {
+            builder = builder.salt(salt);
+        }
+
+        if let Some(preset) = self.preset {
+            builder = builder.preset(preset.into());
+        }
+
+        if let Some(alphabet) = self.alphabet {
+            builder = builder.alphabet(alphabet);
+        }
+
+        if let Some(separators) = self.separators {
+            builder = builder.separators(separators);
+        }
+
+        if let Some(prefix) = self.prefix {
+            builder = builder.prefix(prefix);
+        }
+
+        if let Some(separator) = self.prefix_separator {
+            builder = builder.prefix_separator(separator);
+        }
+
+        builder.build()
+    }
+}
+
+fn main() {
+    let opts = Opts::parse();
+
+    let harsh = match opts.config.build() {
+        Ok(harsh) => harsh,
+        Err(e) => {
+            eprintln!("harsh: {}", e);
+            process::exit(EXIT_BUILD);
+        }
+    };
+
+    let json = opts.json;
+    let result = match opts.command {
+        Command::Encode { values } => run(values, true, |input| encode(&harsh, input, json)),
+        Command::Decode { hashids } => run(hashids, false, |input| decode(&harsh, input, json)),
+        Command::EncodeHex { values } => run(values, false, |input| {
+            respond(json, input, "hex", "hashid", harsh.encode_hex(input))
+        }),
+        Command::DecodeHex { hashids } => run(hashids, false, |input| {
+            respond(json, input, "hashid", "hex", harsh.decode_hex(input))
+        }),
+    };
+
+    match result {
+        Ok(true) => (),
+        Ok(false) => process::exit(EXIT_DECODE),
+        Err(e) => {
+            eprintln!("harsh: {}", e);
+            process::exit(EXIT_IO);
+        }
+    }
+}
+
+/// The result of handling one input: a line of output, or a failure with
+/// an optional line of output describing it.
+type Outcome = Result<String, Option<String>>;
+
+/// Applies `f` to each input, returning whether every input succeeded.
+///
+/// Arguments are handled together when `joined` is set, or one at a time
+/// otherwise. Without arguments, each line of standard input is an input.
+fn run<F>(args: Vec<String>, joined: bool, mut f: F) -> io::Result<bool>
+where
+    F: FnMut(&str) -> Outcome,
+{
+    let stdout = io::stdout();
+    let mut stdout = stdout.lock();
+    let mut success = true;
+
+    let mut handle = |input: &str| -> io::Result<()> {
+        let output = f(input).map(Some).unwrap_or_else(|output| {
+            success = false;
+            output
+        });
+
+        match output {
+            Some(output) => writeln!(stdout, "{}", output),
+            None => Ok(()),
+        }
+    };
+
+    if args.is_empty() {
+        for line in io::stdin().lock().lines() {
+            let line = line?;
+            let line = line.trim();
+            if !line.is_empty() {
+                handle(line)?;
+            }
+        }
+    } else if joined {
+        handle(&args.join(" "))?;
+    } else {
+        for arg in &args {
+            handle(arg)?;
+        }
+    }
+
+    Ok(success)
+}
+
+fn encode(harsh: &Harsh, input: &str, json: bool) -> Outcome {
+    let values: Result<Vec<u64>, _> = input
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|s| !s.is_empty())
+        .map(str::parse)
+        .collect();
+
+    match values {
+        Ok(values) if json => {
+            let hashid = harsh.encode(&values);
+            Ok(json!({ "values": values, "hashid": hashid }).to_string())
+        }
+        Ok(values) => Ok(harsh.encode(&values)),
+        Err(e) => failure(json, input, e),
+    }
+}
+
+fn decode(harsh: &Harsh, input: &str, json: bool) -> Outcome {
+    match harsh.decode(input) {
+        Ok(values) if json => Ok(json!({ "hashid": input, "values": values }).to_string()),
+        Ok(values) => Ok(values
+            .iter()
+            .map(u64::to_string)
+            .collect::<Vec<_>>()
+            .join(" ")),
+        Err(e) => failure(json, input, e),
+    }
+}
+
+fn respond(
+    json: bool,
+    input: &str,
+    input_key: &str,
+    output_key: &str,
+    output: Result<String, harsh::Error>,
+) -> Outcome {
+    match output {
+        Ok(output) if json => Ok(json!({ input_key: input, output_key: output }).to_string()),
+        Ok(output) => Ok(output),
+        Err(e) => failure(json, input, e),
+    }
+}
+
+/// Reports a failed input, on stdout as JSON or on stderr as text.
+fn failure(json: bool, input: &str, error: impl Display) -> Outcome {
+    if json {
+        Err(Some(
+            json!({ "input": input, "error": error.to_string() }).to_string(),
+        ))
+    } else {
+        eprintln!("harsh: {}: {}", input, error);
+        Err(None)
+    }
+}
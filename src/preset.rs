@@ -0,0 +1,64 @@
+/// A ready-made alphabet, with separators to match, for hashids that people
+/// read and type.
+///
+/// Pass one to [`HarshBuilder::preset`](./struct.HarshBuilder.html#method.preset).
+/// An alphabet or separators set on the builder take precedence over those of
+/// the preset.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(::serde::Serialize, ::serde::Deserialize),
+    serde(rename_all = "lowercase")
+)]
+pub enum Preset {
+    /// Douglas Crockford's base 32: digits and uppercase letters, without
+    /// `I`, `L`, `O` and `U`.
+    ///
+    /// Hashids decode in either case, and `O` is read as `0` and `I` and `L`
+    /// as `1`, as the standard recommends.
+    Crockford,
+
+    /// Bitcoin's base 58: digits and letters, without `0`, `O`, `I` and `l`.
+    Base58,
+
+    /// Digits and lowercase letters.
+    Lowercase,
+
+    /// Digits and letters, without any that are easily confused: `0`, `1`,
+    /// `i`, `I`, `l`, `o` and `O`.
+    Unambiguous,
+}
+
+impl Preset {
+    /// Returns the preset's alphabet.
+    pub const fn alphabet(self) -> &'static str {
+        match self {
+            Preset::Crockford => "0123456789ABCDEFGHJKMNPQRSTVWXYZ",
+            Preset::Base58 => "123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz",
+            Preset::Lowercase => "abcdefghijklmnopqrstuvwxyz1234567890",
+            Preset::Unambiguous => "23456789abcdefghjkmnpqrstuvwxyzABCDEFGHJKLMNPQRSTUVWXYZ",
+        }
+    }
+
+    /// Returns the preset's separators, which are the default separators that
+    /// remain in its alphabet.
+    pub const fn separators(self) -> &'static str {
+        match self {
+            Preset::Crockford => "CFHST",
+            Preset::Base58 => "cfhistuCFHSTU",
+            Preset::Lowercase => "cfhistu",
+            Preset::Unambiguous => "cfhstuCFHSTU",
+        }
+    }
+
+    /// Returns the character that `u` should be read as when decoding, which
+    /// is `u` itself unless it is a lookalike of a character in the alphabet.
+    pub(crate) const fn alias(self, u: u8) -> u8 {
+        match (self, u) {
+            (Preset::Crockford, b'o' | b'O') => b'0',
+            (Preset::Crockford, b'i' | b'I' | b'l' | b'L') => b'1',
+            (Preset::Crockford, _) => u.to_ascii_uppercase(),
+            _ => u,
+        }
+    }
+}
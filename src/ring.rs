@@ -0,0 +1,126 @@
+use alloc::{string::String, vec, vec::Vec};
+use core::result;
+
+use crate::harsh::{Error, Harsh};
+
+type Result<T, E = Error> = result::Result<T, E>;
+
+/// A set of `Harsh` configurations tagged with versions, for rotating salts
+/// without breaking hashids handed out under the old ones.
+///
+/// Hashids are always encoded with the primary configuration. When decoding,
+/// the primary is tried first and then each fallback in the order it was
+/// added, and the version that matched is reported along with the values, so
+/// that a hashid from an older version can be redirected to its canonical
+/// form.
+///
+/// ```rust
+/// use harsh::{Harsh, HarshRing};
+///
+/// let old = Harsh::builder().salt("leaked salt").build().unwrap();
+/// let ring = HarshRing::new(2, Harsh::builder().salt("new salt").build().unwrap())
+///     .fallback(1, old.clone());
+///
+/// let decoded = ring.decode(old.encode(&[42])).unwrap();
+/// assert_eq!(1, decoded.version);
+/// assert!(!decoded.is_primary);
+///
+/// let canonical = ring.encode(&decoded.values);
+/// assert_eq!(2, ring.decode(&canonical).unwrap().version);
+/// ```
+#[derive(Clone, Debug)]
+pub struct HarshRing {
+    versions: Vec<(u32, Harsh)>,
+}
+
+/// The values of a hashid decoded by a [`HarshRing`](struct.HarshRing.html),
+/// along with the version whose configuration they were decoded with.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RingMatch {
+    /// The version of the configuration that accepted the hashid.
+    pub version: u32,
+
+    /// The values the hashid holds.
+    pub values: Vec<u64>,
+
+    /// Whether the version is the primary one, in which case the hashid is
+    /// already canonical.
+    pub is_primary: bool,
+}
+
+impl HarshRing {
+    /// Creates a ring that encodes and decodes with `primary`, tagged with
+    /// `version`.
+    pub fn new(version: u32, primary: Harsh) -> Self {
+        HarshRing {
+            versions: vec![(version, primary)],
+        }
+    }
+
+    /// Adds a configuration that hashids are decoded with if every one
+    /// before it fails.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `version` is already in the ring.
+    pub fn fallback(mut self, version: u32, harsh: Harsh) -> Self {
+        assert!(
+            self.versions.iter().all(|&(v, _)| v != version),
+            "version {} is already in the ring",
+            version
+        );
+        self.versions.push((version, harsh));
+        self
+    }
+
+    /// Returns the version of the primary configuration.
+    pub fn version(&self) -> u32 {
+        self.versions[0].0
+    }
+
+    /// Returns the primary configuration.
+    pub fn primary(&self) -> &Harsh {
+        &self.versions[0].1
+    }
+
+    /// Returns the configuration tagged with `version`, if there is one.
+    pub fn get(&self, version: u32) -> Option<&Harsh> {
+        self.versions
+            .iter()
+            .find(|&&(v, _)| v == version)
+            .map(|(_, harsh)| harsh)
+    }
+
+    /// Encodes a slice of `u64` values with the primary configuration.
+    pub fn encode(&self, values: &[u64]) -> String {
+        self.primary().encode(values)
+    }
+
+    /// Decodes a hashid with the first configuration that accepts it.
+    ///
+    /// If none does, the error from the primary configuration is returned.
+    pub fn decode<T: AsRef<str>>(&self, input: T) -> Result<RingMatch> {
+        let input = input.as_ref();
+
+        let error = match self.primary().decode(input) {
+            Ok(values) => return Ok(self.matched(0, values)),
+            Err(e) => e,
+        };
+
+        self.versions
+            .iter()
+            .enumerate()
+            .skip(1)
+            .find_map(|(idx, (_, harsh))| harsh.decode(input).ok().map(|values| (idx, values)))
+            .map(|(idx, values)| self.matched(idx, values))
+            .ok_or(error)
+    }
+
+    fn matched(&self, idx: usize, values: Vec<u64>) -> RingMatch {
+        RingMatch {
+            version: self.versions[idx].0,
+            values,
+            is_primary: idx == 0,
+        }
+    }
+}
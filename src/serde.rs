@@ -0,0 +1,147 @@
+//! Serde support, enabled by the `serde` feature.
+//!
+//! `HarshBuilder` and `HarshConfig` may be serialized and deserialized like
+//! any other configuration. The modules and types here are intended for use
+//! with `#[serde(with = "...")]`, and transparently encode a `u64` field as a
+//! hashid when serializing and decode it again when deserializing.
+//!
+//! ```rust
+//! # use harsh::Harsh;
+//! # use serde::{Deserialize, Serialize};
+//! #[derive(Serialize, Deserialize)]
+//! struct User {
+//!     #[serde(with = "harsh::serde::thread_local")]
+//!     id: u64,
+//!     name: String,
+//! }
+//!
+//! harsh::serde::set_thread_harsh(Harsh::builder().salt("my salt").build().unwrap());
+//! ```
+
+use alloc::string::String;
+use core::marker::PhantomData;
+#[cfg(feature = "std")]
+use std::cell::RefCell;
+
+use ::serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{builder::HarshConfig, Harsh, HarshBuilder, HashId, HashIdKind};
+
+#[cfg(feature = "std")]
+std::thread_local! {
+    static THREAD_HARSH: RefCell<Option<Harsh>> = const { RefCell::new(None) };
+}
+
+/// Sets the `Harsh` used by [`thread_local`](./thread_local/index.html) on
+/// the current thread, returning the previous instance, if any.
+#[cfg(feature = "std")]
+pub fn set_thread_harsh(harsh: Harsh) -> Option<Harsh> {
+    THREAD_HARSH.with(|cell| cell.borrow_mut().replace(harsh))
+}
+
+/// Encodes and decodes with the `Harsh` set for the current thread by
+/// [`set_thread_harsh`](../fn.set_thread_harsh.html).
+///
+/// Serializing or deserializing on a thread without a `Harsh` is an error.
+#[cfg(feature = "std")]
+pub mod thread_local {
+    use alloc::string::String;
+
+    use ::serde::{de, ser, Deserialize, Deserializer, Serializer};
+
+    use super::THREAD_HARSH;
+    use crate::Harsh;
+
+    static MISSING_HARSH_MESSAGE: &str = "no Harsh instance has been set for this thread";
+
+    fn with_harsh<T>(f: impl FnOnce(&Harsh) -> T) -> Option<T> {
+        THREAD_HARSH.with(|cell| cell.borrow().as_ref().map(f))
+    }
+
+    pub fn serialize<S: Serializer>(value: &u64, serializer: S) -> Result<S::Ok, S::Error> {
+        match with_harsh(|harsh| harsh.encode(&[*value])) {
+            Some(hash) => serializer.serialize_str(&hash),
+            None => Err(ser::Error::custom(MISSING_HARSH_MESSAGE)),
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u64, D::Error> {
+        let hash = String::deserialize(deserializer)?;
+        with_harsh(|harsh| super::decode_single(harsh, &hash))
+            .unwrap_or_else(|| Err(de::Error::custom(MISSING_HARSH_MESSAGE)))
+    }
+}
+
+/// Provides the `Harsh` used by [`Using`](./struct.Using.html).
+pub trait HarshSource {
+    fn harsh() -> &'static Harsh;
+}
+
+/// Encodes and decodes with the `Harsh` provided by `S`.
+///
+/// ```rust
+/// # use harsh::{serde::HarshSource, Harsh};
+/// # use serde::{Deserialize, Serialize};
+/// # use std::sync::OnceLock;
+/// struct Ids;
+///
+/// impl HarshSource for Ids {
+///     fn harsh() -> &'static Harsh {
+///         static HARSH: OnceLock<Harsh> = OnceLock::new();
+///         HARSH.get_or_init(|| Harsh::builder().salt("my salt").build().unwrap())
+///     }
+/// }
+///
+/// #[derive(Serialize, Deserialize)]
+/// struct User {
+///     #[serde(with = "harsh::serde::Using::<Ids>")]
+///     id: u64,
+/// }
+/// ```
+pub struct Using<S>(PhantomData<S>);
+
+impl<H: HarshSource> Using<H> {
+    pub fn serialize<S: Serializer>(value: &u64, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&H::harsh().encode(&[*value]))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u64, D::Error> {
+        let hash = String::deserialize(deserializer)?;
+        decode_single(H::harsh(), &hash)
+    }
+}
+
+impl Serialize for HarshBuilder {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        HarshConfig::from(self).serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for HarshBuilder {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        HarshConfig::deserialize(deserializer).map(HarshBuilder::from)
+    }
+}
+
+impl<T: HashIdKind> Serialize for HashId<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de, T: HashIdKind> Deserialize<'de> for HashId<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let hash = String::deserialize(deserializer)?;
+        hash.parse().map_err(de::Error::custom)
+    }
+}
+
+fn decode_single<E: de::Error>(harsh: &Harsh, hash: &str) -> Result<u64, E> {
+    match harsh.decode(hash).map_err(E::custom)?[..] {
+        [value] => Ok(value),
+        ref values => Err(E::invalid_length(
+            values.len(),
+            &"a hashid holding one value",
+        )),
+    }
+}
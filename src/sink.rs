@@ -0,0 +1,275 @@
+use alloc::boxed::Box;
+use core::{fmt, str::Chars};
+
+/// Receives the characters of a hashid as they are produced.
+///
+/// Returning an error stops the hashid from being produced any further.
+pub(crate) trait Sink {
+    fn push(&mut self, c: char) -> fmt::Result;
+
+    fn push_all(&mut self, values: &[char]) -> fmt::Result {
+        values.iter().try_for_each(|&c| self.push(c))
+    }
+}
+
+/// Writes a hashid to a `fmt::Write`.
+pub(crate) struct Writer<'a, W: ?Sized>(pub &'a mut W);
+
+impl<W: fmt::Write + ?Sized> Sink for Writer<'_, W> {
+    #[inline]
+    fn push(&mut self, c: char) -> fmt::Result {
+        self.0.write_char(c)
+    }
+}
+
+/// Writes a hashid to a byte buffer as UTF-8, failing if the buffer is too
+/// small.
+pub(crate) struct Buffer<'a> {
+    pub buffer: &'a mut [u8],
+    pub len: usize,
+}
+
+impl Sink for Buffer<'_> {
+    #[inline]
+    fn push(&mut self, c: char) -> fmt::Result {
+        let width = c.len_utf8();
+        let slot = self
+            .buffer
+            .get_mut(self.len..self.len + width)
+            .ok_or(fmt::Error)?;
+        c.encode_utf8(slot);
+        self.len += width;
+        Ok(())
+    }
+}
+
+/// Measures a hashid, keeping a copy of it if it is short enough.
+pub(crate) struct Measure {
+    pub len: usize,
+    buffer: [char; MEASURE_CAPACITY],
+}
+
+const MEASURE_CAPACITY: usize = 64;
+
+impl Measure {
+    pub fn new() -> Self {
+        Measure {
+            len: 0,
+            buffer: ['\0'; MEASURE_CAPACITY],
+        }
+    }
+
+    /// Returns the character at `idx`, which must be retained.
+    pub fn get(&self, idx: usize) -> char {
+        self.buffer[idx]
+    }
+
+    /// Returns the hashid, unless it was too long to keep.
+    pub fn retained(&self) -> Option<&[char]> {
+        self.buffer.get(..self.len)
+    }
+}
+
+impl Sink for Measure {
+    #[inline]
+    fn push(&mut self, c: char) -> fmt::Result {
+        if let Some(slot) = self.buffer.get_mut(self.len) {
+            *slot = c;
+        }
+        self.len += 1;
+        Ok(())
+    }
+}
+
+/// Fails as soon as the hashid differs from an expected one.
+///
+/// `is_match` is given each expected character and the character produced,
+/// so that the expected hashid may be written in another case.
+pub(crate) struct Compare<'a, F> {
+    expected: Chars<'a>,
+    is_match: F,
+}
+
+impl<'a, F: Fn(char, char) -> bool> Compare<'a, F> {
+    pub fn new(expected: &'a str, is_match: F) -> Self {
+        Compare {
+            expected: expected.chars(),
+            is_match,
+        }
+    }
+
+    /// Returns whether the whole of the expected hashid has been matched.
+    pub fn is_complete(&self) -> bool {
+        self.expected.as_str().is_empty()
+    }
+}
+
+impl<F: Fn(char, char) -> bool> Sink for Compare<'_, F> {
+    #[inline]
+    fn push(&mut self, c: char) -> fmt::Result {
+        match self.expected.next() {
+            Some(expected) if (self.is_match)(expected, c) => Ok(()),
+            _ => Err(fmt::Error),
+        }
+    }
+}
+
+/// Passes on only the `take` characters following the first `skip`.
+pub(crate) struct Window<'a, S> {
+    pub sink: &'a mut S,
+    pub skip: usize,
+    pub take: usize,
+}
+
+impl<S: Sink> Sink for Window<'_, S> {
+    #[inline]
+    fn push(&mut self, c: char) -> fmt::Result {
+        if self.skip > 0 {
+            self.skip -= 1;
+            Ok(())
+        } else if self.take > 0 {
+            self.take -= 1;
+            self.sink.push(c)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// Appends a Luhn mod N check character to a hashid.
+///
+/// Each character's code point is its position in `tables` taken end to end,
+/// and N is the number of characters in them all. Since the hashid's length
+/// isn't known until it ends, sums are kept for both possible positions of
+/// the doubled characters.
+pub(crate) struct Checksum<'a, S> {
+    sink: &'a mut S,
+    tables: &'a [&'a [char]],
+    sums: [usize; 2],
+    len: usize,
+}
+
+impl<'a, S: Sink> Checksum<'a, S> {
+    pub fn new(sink: &'a mut S, tables: &'a [&'a [char]]) -> Self {
+        Checksum {
+            sink,
+            tables,
+            sums: [0; 2],
+            len: 0,
+        }
+    }
+
+    /// Writes the check character.
+    pub fn finish(self) -> fmt::Result {
+        let n = characters(self.tables);
+        let sum = self.sums[(self.len + 1) % 2] % n;
+        let c = character(self.tables, (n - sum) % n);
+        self.sink.push(c)
+    }
+}
+
+impl<S: Sink> Sink for Checksum<'_, S> {
+    fn push(&mut self, c: char) -> fmt::Result {
+        let n = characters(self.tables);
+        let code_point = code_point(self.tables, c).ok_or(fmt::Error)?;
+        let doubled = code_point * 2;
+
+        // Counting back from the check character, every second character is
+        // doubled, starting with the one just before it.
+        self.sums[self.len % 2] += doubled / n + doubled % n;
+        self.sums[(self.len + 1) % 2] += code_point;
+        self.len += 1;
+        self.sink.push(c)
+    }
+}
+
+/// Returns whether `input`, the last character of which is its check
+/// character, passes the Luhn mod N check.
+///
+/// Each character is passed through `fold` before it is looked up.
+pub(crate) fn is_checksum_valid(
+    tables: &[&[char]],
+    input: &str,
+    fold: impl Fn(char) -> char,
+) -> bool {
+    let n = characters(tables);
+    let mut sum = 0;
+
+    for (idx, c) in input.chars().rev().enumerate() {
+        let code_point = match code_point(tables, fold(c)) {
+            Some(code_point) => code_point,
+            None => return false,
+        };
+
+        let addend = code_point * (1 + idx % 2);
+        sum += addend / n + addend % n;
+    }
+
+    !input.is_empty() && sum % n == 0
+}
+
+fn characters(tables: &[&[char]]) -> usize {
+    tables.iter().map(|table| table.len()).sum()
+}
+
+fn code_point(tables: &[&[char]], c: char) -> Option<usize> {
+    let mut offset = 0;
+    for table in tables {
+        if let Some(idx) = table.iter().position(|&t| t == c) {
+            return Some(offset + idx);
+        }
+        offset += table.len();
+    }
+    None
+}
+
+fn character(tables: &[&[char]], mut code_point: usize) -> char {
+    for table in tables {
+        if code_point < table.len() {
+            return table[code_point];
+        }
+        code_point -= table.len();
+    }
+    unreachable!("code point is out of range")
+}
+
+/// Fails as soon as the hashid contains a blocked word.
+///
+/// Only the last `MAXIMUM_WORD_LENGTH` characters are retained, so longer
+/// words must be discarded before they reach the scanner.
+pub(crate) struct Scanner<'a> {
+    words: &'a [Box<[char]>],
+    window: [char; MAXIMUM_WORD_LENGTH],
+    len: usize,
+}
+
+pub(crate) const MAXIMUM_WORD_LENGTH: usize = 32;
+
+impl<'a> Scanner<'a> {
+    pub fn new(words: &'a [Box<[char]>]) -> Self {
+        Scanner {
+            words,
+            window: ['\0'; MAXIMUM_WORD_LENGTH],
+            len: 0,
+        }
+    }
+}
+
+impl Sink for Scanner<'_> {
+    fn push(&mut self, c: char) -> fmt::Result {
+        if self.len == MAXIMUM_WORD_LENGTH {
+            self.window.copy_within(1.., 0);
+            self.len -= 1;
+        }
+
+        self.window[self.len] = c.to_ascii_lowercase();
+        self.len += 1;
+
+        let window = &self.window[..self.len];
+        if self.words.iter().any(|word| window.ends_with(word)) {
+            Err(fmt::Error)
+        } else {
+            Ok(())
+        }
+    }
+}
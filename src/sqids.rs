@@ -0,0 +1,324 @@
+use alloc::{boxed::Box, string::String, vec, vec::Vec};
+use core::{cmp, result};
+
+use crate::{
+    blocklist::{self, SQIDS_BLOCKLIST},
+    builder::BuildError,
+    harsh::{DecodeError, Error},
+};
+
+const DEFAULT_ALPHABET: &[u8] = b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789";
+const MINIMUM_ALPHABET_LENGTH: usize = 3;
+const MAXIMUM_MIN_LENGTH: usize = 255;
+
+type Result<T, E = Error> = result::Result<T, E>;
+
+/// A builder used to configure and create a Sqids instance.
+#[derive(Debug, Default)]
+pub struct SqidsBuilder {
+    alphabet: Option<String>,
+    blocklist: Option<Vec<String>>,
+    min_length: usize,
+}
+
+impl SqidsBuilder {
+    /// Creates a new `SqidsBuilder` instance.
+    pub fn new() -> SqidsBuilder {
+        SqidsBuilder {
+            alphabet: None,
+            blocklist: None,
+            min_length: 0,
+        }
+    }
+
+    /// Provides an alphabet.
+    ///
+    /// The alphabet must consist of at least three unique ASCII characters.
+    pub fn alphabet<T: Into<String>>(mut self, alphabet: T) -> SqidsBuilder {
+        self.alphabet = Some(alphabet.into());
+        self
+    }
+
+    /// Provides a blocklist, replacing the default one from the Sqids
+    /// specification.
+    ///
+    /// Words shorter than three characters or containing characters outside
+    /// the alphabet can never appear in an id and are discarded.
+    pub fn blocklist<I, T>(mut self, blocklist: I) -> SqidsBuilder
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<String>,
+    {
+        self.blocklist = Some(blocklist.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Provides a minimum id length, which may not exceed 255.
+    pub fn length(mut self, min_length: usize) -> SqidsBuilder {
+        self.min_length = min_length;
+        self
+    }
+
+    /// Initializes a new `Sqids` based on the `SqidsBuilder`.
+    ///
+    /// This method will consume the `SqidsBuilder`.
+    pub fn build(self) -> Result<Sqids, BuildError> {
+        let alphabet = match self.alphabet {
+            None => DEFAULT_ALPHABET.to_vec(),
+            Some(alphabet) => {
+                if let Some(c) = alphabet.chars().find(|c| !c.is_ascii()) {
+                    return Err(BuildError::IllegalCharacter(c));
+                }
+                alphabet.into_bytes()
+            }
+        };
+
+        if alphabet.len() < MINIMUM_ALPHABET_LENGTH {
+            return Err(BuildError::AlphabetLength);
+        }
+
+        for (idx, &item) in alphabet.iter().enumerate() {
+            if alphabet[..idx].contains(&item) {
+                return Err(BuildError::DuplicateCharacter(item as char));
+            }
+        }
+
+        if self.min_length > MAXIMUM_MIN_LENGTH {
+            return Err(BuildError::Length(self.min_length));
+        }
+
+        let characters = alphabet.iter().map(|&u| u as char);
+        let blocklist = match self.blocklist {
+            None => blocklist::filter(SQIDS_BLOCKLIST.iter().copied(), characters),
+            Some(ref words) => blocklist::filter(words.iter().map(String::as_str), characters),
+        };
+        let blocklist = blocklist
+            .into_iter()
+            .map(|word| word.into_bytes().into_boxed_slice())
+            .collect::<Vec<_>>();
+
+        Ok(Sqids {
+            alphabet: shuffle(alphabet).into_boxed_slice(),
+            blocklist: blocklist.into_boxed_slice(),
+            min_length: self.min_length,
+        })
+    }
+}
+
+/// A Sqids-compatible hasher.
+///
+/// Sqids is the successor to the Hashids scheme. Ids produced by this type
+/// match those produced by the other Sqids implementations given the same
+/// alphabet, minimum length and blocklist.
+#[derive(Clone, Debug)]
+pub struct Sqids {
+    alphabet: Box<[u8]>,
+    blocklist: Box<[Box<[u8]>]>,
+    min_length: usize,
+}
+
+impl Sqids {
+    /// Create a default instance of Sqids.
+    pub fn new() -> Self {
+        SqidsBuilder::new()
+            .build()
+            .expect("Default options should not fail")
+    }
+
+    /// Build a new instance of Sqids.
+    pub fn builder() -> SqidsBuilder {
+        SqidsBuilder::new()
+    }
+
+    /// Encodes a slice of `u64` values into a single id.
+    ///
+    /// Encoding fails only when every possible id for the given values is
+    /// blocked, which requires a pathological blocklist.
+    pub fn encode(&self, values: &[u64]) -> Result<String> {
+        if values.is_empty() {
+            return Ok(String::new());
+        }
+
+        (0..=self.alphabet.len())
+            .map(|increment| self.encode_values(values, increment))
+            .find(|id| !self.is_blocked(id.as_bytes()))
+            .ok_or(Error::Blocklist)
+    }
+
+    /// Decodes a single id into a vector of `u64` values.
+    ///
+    /// An empty id decodes to an empty vector.
+    pub fn decode<T: AsRef<str>>(&self, input: T) -> Result<Vec<u64>> {
+        let text = input.as_ref();
+        let input = text.as_bytes();
+        let mut result = Vec::new();
+
+        let is_character = |c: char| c.is_ascii() && self.alphabet.contains(&(c as u8));
+        if let Some((index, character)) = text.char_indices().find(|&(_, c)| !is_character(c)) {
+            return Err(DecodeError::Character { index, character }.into());
+        }
+
+        let (&prefix, mut input) = match input.split_first() {
+            Some(parts) => parts,
+            None => return Ok(result),
+        };
+
+        let offset = self
+            .alphabet
+            .iter()
+            .position(|&u| u == prefix)
+            .expect("every character is in the alphabet");
+        let mut alphabet = rotate(&self.alphabet, offset);
+        alphabet.reverse();
+
+        while !input.is_empty() {
+            let separator = alphabet[0];
+            let (chunk, rest) = match input.iter().position(|&u| u == separator) {
+                Some(idx) => (&input[..idx], Some(&input[idx + 1..])),
+                None => (input, None),
+            };
+
+            if chunk.is_empty() {
+                break;
+            }
+
+            let value = to_number(chunk, &alphabet[1..]).ok_or(DecodeError::Overflow)?;
+            result.push(value);
+
+            match rest {
+                Some(rest) => {
+                    alphabet = shuffle(alphabet);
+                    input = rest;
+                }
+                None => break,
+            }
+        }
+
+        Ok(result)
+    }
+
+    fn encode_values(&self, values: &[u64], increment: usize) -> String {
+        let length = self.alphabet.len();
+        let offset = values
+            .iter()
+            .enumerate()
+            .fold(values.len(), |a, (idx, &value)| {
+                self.alphabet[(value % length as u64) as usize] as usize + idx + a
+            });
+
+        let mut alphabet = rotate(&self.alphabet, (offset + increment) % length);
+        let mut buffer = vec![alphabet[0]];
+        alphabet.reverse();
+
+        for (idx, &value) in values.iter().enumerate() {
+            push_id(&mut buffer, value, &alphabet[1..]);
+
+            if idx + 1 < values.len() {
+                buffer.push(alphabet[0]);
+                alphabet = shuffle(alphabet);
+            }
+        }
+
+        if buffer.len() < self.min_length {
+            buffer.push(alphabet[0]);
+
+            while buffer.len() < self.min_length {
+                alphabet = shuffle(alphabet);
+                let take = cmp::min(self.min_length - buffer.len(), alphabet.len());
+                buffer.extend_from_slice(&alphabet[..take]);
+            }
+        }
+
+        String::from_utf8(buffer).expect("alphabet is ascii")
+    }
+
+    fn is_blocked(&self, id: &[u8]) -> bool {
+        let id = id.to_ascii_lowercase();
+
+        self.blocklist.iter().any(|word| {
+            if word.len() > id.len() {
+                false
+            } else if id.len() <= 3 || word.len() <= 3 {
+                id == **word
+            } else if word.iter().any(u8::is_ascii_digit) {
+                id.starts_with(word) || id.ends_with(word)
+            } else {
+                id.windows(word.len()).any(|window| window == &**word)
+            }
+        })
+    }
+}
+
+impl Default for Sqids {
+    fn default() -> Self {
+        Sqids::new()
+    }
+}
+
+fn shuffle(mut values: Vec<u8>) -> Vec<u8> {
+    let length = values.len();
+
+    for i in 0..length - 1 {
+        let j = length - 1 - i;
+        let r = (i * j + values[i] as usize + values[j] as usize) % length;
+        values.swap(i, r);
+    }
+
+    values
+}
+
+fn rotate(values: &[u8], offset: usize) -> Vec<u8> {
+    let mut values = values.to_vec();
+    values.rotate_left(offset);
+    values
+}
+
+fn push_id(buffer: &mut Vec<u8>, mut value: u64, alphabet: &[u8]) {
+    let length = alphabet.len() as u64;
+    let start = buffer.len();
+
+    loop {
+        buffer.push(alphabet[(value % length) as usize]);
+        value /= length;
+
+        if value == 0 {
+            buffer[start..].reverse();
+            return;
+        }
+    }
+}
+
+fn to_number(input: &[u8], alphabet: &[u8]) -> Option<u64> {
+    input.iter().try_fold(0u64, |a, &value| {
+        let pos = alphabet.iter().position(|&item| item == value)? as u64;
+        a.checked_mul(alphabet.len() as u64)?.checked_add(pos)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+
+    #[test]
+    fn to_number() {
+        assert_eq!(
+            Some(22),
+            super::to_number(b"w", b"abcdefghijklmnopqrstuvwxyz")
+        );
+        assert_eq!(None, super::to_number(b"w!", b"abcdefghijklmnopqrstuvwxyz"));
+    }
+
+    #[test]
+    fn is_blocked() {
+        let sqids = super::Sqids::builder()
+            .blocklist(vec!["bad", "w0rd", "worse"])
+            .build()
+            .unwrap();
+
+        assert!(sqids.is_blocked(b"BAD"));
+        assert!(!sqids.is_blocked(b"xbad"));
+        assert!(sqids.is_blocked(b"w0rdxyz"));
+        assert!(!sqids.is_blocked(b"xw0rdx"));
+        assert!(sqids.is_blocked(b"xyWorSEz"));
+    }
+}
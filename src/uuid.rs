@@ -0,0 +1,34 @@
+/// A type holding the 128 bits of a UUID.
+///
+/// This is implemented for `[u8; 16]` and, with the `uuid` feature enabled,
+/// for `uuid::Uuid`, so that either may be passed to
+/// [`Harsh::encode_uuid`](./struct.Harsh.html#method.encode_uuid) and
+/// returned from [`Harsh::decode_uuid`](./struct.Harsh.html#method.decode_uuid).
+pub trait UuidBytes: Sized {
+    /// Returns the big-endian bytes of the UUID.
+    fn to_uuid_bytes(&self) -> [u8; 16];
+
+    /// Creates a UUID from its big-endian bytes.
+    fn from_uuid_bytes(bytes: [u8; 16]) -> Self;
+}
+
+impl UuidBytes for [u8; 16] {
+    fn to_uuid_bytes(&self) -> [u8; 16] {
+        *self
+    }
+
+    fn from_uuid_bytes(bytes: [u8; 16]) -> Self {
+        bytes
+    }
+}
+
+#[cfg(feature = "uuid")]
+impl UuidBytes for ::uuid::Uuid {
+    fn to_uuid_bytes(&self) -> [u8; 16] {
+        *self.as_bytes()
+    }
+
+    fn from_uuid_bytes(bytes: [u8; 16]) -> Self {
+        ::uuid::Uuid::from_bytes(bytes)
+    }
+}
@@ -0,0 +1,65 @@
+#![cfg(feature = "num-bigint")]
+
+use harsh::Harsh;
+use num_bigint::BigUint;
+
+fn content_hash() -> BigUint {
+    BigUint::parse_bytes(
+        b"e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855",
+        16,
+    )
+    .unwrap()
+}
+
+#[test]
+fn small_values_match_u64() {
+    let harsh = Harsh::builder().salt("this is my salt").build().unwrap();
+    let cases: [&[u64]; 4] = [&[0], &[1, 2, 3], &[u64::MAX], &[1226198605112, 0, 5]];
+
+    for &values in &cases {
+        let big: Vec<BigUint> = values.iter().map(|&n| BigUint::from(n)).collect();
+        let hash = harsh.encode(values);
+
+        assert_eq!(hash, harsh.encode_biguint(&big));
+        assert_eq!(big, harsh.decode_biguint(&hash).unwrap());
+    }
+}
+
+#[test]
+fn u128_values_match_u128() {
+    let harsh = Harsh::builder().salt("this is my salt").build().unwrap();
+    let values = [u64::MAX as u128 + 1, u128::MAX];
+    let big: Vec<BigUint> = values.iter().map(|&n| BigUint::from(n)).collect();
+
+    assert_eq!(harsh.encode_u128(&values), harsh.encode_biguint(&big));
+}
+
+#[test]
+fn content_hash_round_trip() {
+    let harsh = Harsh::builder().salt("this is my salt").build().unwrap();
+    let values = vec![content_hash(), BigUint::from(7u8), content_hash()];
+
+    let hash = harsh.encode_biguint(&values);
+    assert_eq!(values, harsh.decode_biguint(&hash).unwrap());
+}
+
+#[test]
+fn very_large_values_round_trip() {
+    let harsh = Harsh::builder()
+        .salt("this is my salt")
+        .length(20)
+        .build()
+        .unwrap();
+    let values = vec![BigUint::from(1u8) << 4096u32, BigUint::from(0u8)];
+
+    let hash = harsh.encode_biguint(&values);
+    assert_eq!(values, harsh.decode_biguint(&hash).unwrap());
+}
+
+#[test]
+fn large_values_overflow_u128() {
+    let harsh = Harsh::builder().salt("this is my salt").build().unwrap();
+    let hash = harsh.encode_biguint(&[content_hash()]);
+
+    assert!(harsh.decode_u128(&hash).is_err());
+}
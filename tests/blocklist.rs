@@ -0,0 +1,131 @@
+use harsh::{Error, Harsh};
+
+#[test]
+fn blocklist_is_disabled_by_default() {
+    let harsh = Harsh::default();
+
+    assert_eq!("poop", harsh.encode(&[31707]));
+    assert_eq!(&[31707], &harsh.decode("poop").unwrap()[..]);
+}
+
+#[test]
+fn default_words_are_regenerated() {
+    let harsh = Harsh::builder()
+        .blocklist(Vec::<String>::new())
+        .build()
+        .unwrap();
+
+    assert_eq!("vxxy", harsh.encode(&[31707]));
+    assert_eq!(&[31707], &harsh.decode("vxxy").unwrap()[..]);
+    assert!(
+        harsh.decode("poop").is_err(),
+        "blocked hashid should not decode"
+    );
+}
+
+#[test]
+fn custom_words_are_case_insensitive() {
+    let harsh = Harsh::builder().blocklist(vec!["O2F"]).build().unwrap();
+
+    assert_eq!("rkfAh4", harsh.encode(&[1, 2, 3]));
+    assert_eq!(&[1, 2, 3], &harsh.decode("rkfAh4").unwrap()[..]);
+    assert!(
+        harsh.decode("o2fXhV").is_err(),
+        "blocked hashid should not decode"
+    );
+}
+
+#[test]
+fn words_outside_ascii_fold_like_hashids() {
+    const ALPHABET: &str = "abcdefghijklmnopqrstuvwxyzÀÉÎÕÜ";
+    let plain = Harsh::builder().alphabet(ALPHABET).build().unwrap();
+
+    let (value, word) = (0..)
+        .filter_map(|value| {
+            let hash = plain.encode(&[value]);
+            let start = hash.find('É')?;
+            let word = hash[start..].chars().take(3).collect::<String>();
+            Some((value, word)).filter(|(_, word)| word.chars().count() == 3)
+        })
+        .next()
+        .unwrap();
+
+    // Only the ASCII letters of the word change case, as in the hashid.
+    let harsh = Harsh::builder()
+        .alphabet(ALPHABET)
+        .blocklist(vec![word.to_uppercase()])
+        .build()
+        .unwrap();
+
+    let hash = harsh.encode(&[value]);
+    assert!(
+        !hash.to_ascii_lowercase().contains(&word),
+        "{} was not filtered",
+        hash
+    );
+    assert_eq!(vec![value], harsh.decode(&hash).unwrap());
+}
+
+#[test]
+fn blocklist_round_trip() {
+    let harsh = Harsh::builder()
+        .blocklist(vec!["xyz"])
+        .length(8)
+        .build()
+        .unwrap();
+
+    for n in 0..10_000 {
+        let hash = harsh.encode(&[n, n + 1]);
+        assert!(
+            !hash.to_lowercase().contains("poo"),
+            "{} was not filtered",
+            hash
+        );
+        assert_eq!(&[n, n + 1], &harsh.decode(&hash).unwrap()[..]);
+    }
+}
+
+#[test]
+fn every_candidate_blocked() {
+    let mut words = Vec::new();
+
+    let harsh = loop {
+        let harsh = Harsh::builder().blocklist(words.clone()).build().unwrap();
+        match harsh.try_encode(&[1_000_000]) {
+            Ok(hash) => {
+                assert_eq!(harsh.encode(&[1_000_000]), hash);
+                words.push(hash);
+            }
+            Err(Error::Blocklist) => break harsh,
+            Err(e) => panic!("unexpected error {:?}", e),
+        }
+    };
+
+    assert_eq!(
+        Harsh::default().encode(&[1_000_000]),
+        harsh.encode(&[1_000_000])
+    );
+}
+
+#[test]
+fn candidates_step_three_lottery_characters() {
+    let mut words = Vec::new();
+
+    let harsh = loop {
+        let harsh = Harsh::builder().blocklist(words.clone()).build().unwrap();
+        match harsh.try_encode(&[1_000_000]) {
+            Ok(hash) => words.push(hash),
+            Err(Error::Blocklist) => break harsh,
+            Err(e) => panic!("unexpected error {:?}", e),
+        }
+    };
+
+    // Every third character of the 44 left in the default alphabet.
+    let lottery = words
+        .iter()
+        .map(|word| word.chars().next().unwrap())
+        .collect::<String>();
+    assert_eq!("glorxAEKNQWZ369", lottery);
+    assert_eq!(["gQ3E6", "lM3gD", "o65l7"], words[..3]);
+    assert_eq!("gQ3E6", harsh.encode(&[1_000_000]));
+}
@@ -0,0 +1,93 @@
+use harsh::{DecodeError, Error, Harsh};
+
+const PAYLOADS: [&[u8]; 7] = [
+    &[],
+    &[0],
+    &[0, 0, 0],
+    &[0, 0, 1],
+    &[0xde, 0xad, 0xbe, 0xef],
+    &[0xff; 8],
+    &[1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17],
+];
+
+#[test]
+fn bytes_round_trip() {
+    let harsh = Harsh::builder().salt("this is my salt").build().unwrap();
+
+    for &payload in &PAYLOADS {
+        let hash = harsh.encode_bytes(payload);
+        assert_eq!(payload, &harsh.decode_bytes(&hash).unwrap()[..]);
+    }
+}
+
+#[test]
+fn every_length_round_trips() {
+    let harsh = Harsh::builder().salt("this is my salt").build().unwrap();
+    let payload: Vec<u8> = (0..=40).map(|n: u8| n.wrapping_mul(37)).collect();
+
+    for len in 0..payload.len() {
+        let hash = harsh.encode_bytes(&payload[..len]);
+        assert_eq!(&payload[..len], &harsh.decode_bytes(&hash).unwrap()[..]);
+    }
+}
+
+#[test]
+fn leading_zeros_are_kept() {
+    let harsh = Harsh::builder().salt("this is my salt").build().unwrap();
+    let hashes: Vec<String> = (0..10)
+        .map(|len| harsh.encode_bytes(&vec![0; len]))
+        .collect();
+
+    for (idx, hash) in hashes.iter().enumerate() {
+        assert!(!hashes[idx + 1..].contains(hash), "{}", hash);
+    }
+
+    assert_ne!(harsh.encode_bytes(&[1]), harsh.encode_bytes(&[0, 1]));
+}
+
+#[test]
+fn bytes_is_shorter_than_hex() {
+    let harsh = Harsh::default();
+    let hash = harsh.encode_bytes(&[
+        0x50, 0x7f, 0x1f, 0x77, 0xbc, 0xf8, 0x6c, 0xd7, 0x99, 0x43, 0x90, 0x11,
+    ]);
+    let hex = harsh.encode_hex("507f1f77bcf86cd799439011").unwrap();
+
+    assert!(
+        hash.len() < hex.len(),
+        "{} is not shorter than {}",
+        hash,
+        hex
+    );
+}
+
+#[test]
+fn other_hashids_are_rejected() {
+    let harsh = Harsh::builder().salt("this is my salt").build().unwrap();
+
+    // Values that would otherwise mark out whole bytes.
+    for values in &[&[1][..], &[300], &[0x1ff], &[1 << 16 | 0xbeef]] {
+        assert!(matches!(
+            harsh.decode_bytes(harsh.encode(values)),
+            Err(Error::Bytes)
+        ));
+    }
+    assert!(matches!(
+        harsh.decode_bytes(harsh.encode_set(&[1, 2, 3]).unwrap()),
+        Err(Error::Bytes)
+    ));
+}
+
+#[test]
+fn bytes_do_not_decode_as_values() {
+    let harsh = Harsh::builder().salt("this is my salt").build().unwrap();
+
+    for &payload in &PAYLOADS {
+        let hash = harsh.encode_bytes(payload);
+        assert!(matches!(
+            harsh.decode_u128(&hash),
+            Err(Error::Decode(DecodeError::Mismatch))
+        ));
+        assert!(harsh.decode_set(&hash).is_err());
+    }
+}
@@ -0,0 +1,121 @@
+use harsh::{harsh, BuildError, Harsh};
+
+const INSENSITIVE: Harsh = harsh!(salt = "this is my salt", case_insensitive = true);
+
+fn insensitive() -> Harsh {
+    Harsh::builder()
+        .salt("this is my salt")
+        .case_insensitive(true)
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn encodes_in_lowercase() {
+    let harsh = insensitive();
+
+    for value in 0..1000 {
+        let hash = harsh.encode(&[value, 42]);
+        assert_eq!(hash.to_lowercase(), hash);
+    }
+}
+
+#[test]
+fn decodes_in_any_case() {
+    let harsh = insensitive();
+
+    for value in 0..1000 {
+        let hash = harsh.encode(&[value, 42]);
+        let mixed = hash
+            .chars()
+            .enumerate()
+            .map(|(idx, c)| {
+                if idx % 2 == 0 {
+                    c.to_ascii_uppercase()
+                } else {
+                    c
+                }
+            })
+            .collect::<String>();
+
+        assert_eq!(
+            &[value, 42],
+            &harsh.decode(hash.to_uppercase()).unwrap()[..]
+        );
+        assert_eq!(&[value, 42], &harsh.decode(&mixed).unwrap()[..]);
+    }
+}
+
+#[test]
+fn decodes_in_any_case_with_padding_prefix_and_checksum() {
+    let harsh = Harsh::builder()
+        .length(10)
+        .prefix("Usr")
+        .checksum(true)
+        .case_insensitive(true)
+        .build()
+        .unwrap();
+
+    for value in 0..1000 {
+        let hash = harsh.encode(&[value]);
+
+        assert!(hash.starts_with("Usr_"));
+        assert_eq!(&[value], &harsh.decode(hash.to_lowercase()).unwrap()[..]);
+        assert_eq!(&[value], &harsh.decode(hash.to_uppercase()).unwrap()[..]);
+    }
+}
+
+#[test]
+fn case_sensitive_by_default() {
+    let harsh = Harsh::builder().salt("this is my salt").build().unwrap();
+    let hash = harsh.encode(&[1, 2, 3]);
+
+    assert!(harsh.decode(hash.to_uppercase()).is_err());
+}
+
+#[test]
+fn collapsed_alphabet_is_rejected() {
+    let result = Harsh::builder()
+        .alphabet("abcdefghABCDEFGH12")
+        .case_insensitive(true)
+        .build();
+
+    assert!(matches!(result, Err(BuildError::AlphabetLength)));
+}
+
+#[test]
+fn only_ascii_letters_are_folded() {
+    let harsh = Harsh::builder()
+        .alphabet("abcdefghijklmnopqrstuvwxyzÀÉÎÕÜ")
+        .case_insensitive(true)
+        .build()
+        .unwrap();
+
+    let (value, hash) = (0..)
+        .map(|value| (value, harsh.encode(&[value])))
+        .find(|(_, hash)| hash.contains('É'))
+        .unwrap();
+
+    assert_eq!(vec![value], harsh.decode(hash.to_uppercase()).unwrap());
+    assert!(harsh.decode(hash.to_lowercase()).is_err());
+}
+
+#[test]
+fn prefix_separator_folding_into_alphabet_is_rejected() {
+    let result = Harsh::builder()
+        .alphabet("abcdefghijklmnopqrstuvwxyz")
+        .prefix("usr")
+        .prefix_separator('A')
+        .case_insensitive(true)
+        .build();
+
+    assert!(matches!(result, Err(BuildError::PrefixSeparator('A'))));
+}
+
+#[test]
+fn macro_case_insensitive_matches_builder() {
+    assert_eq!(
+        insensitive().encode(&[1, 2, 3]),
+        INSENSITIVE.encode(&[1, 2, 3])
+    );
+}
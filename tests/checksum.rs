@@ -0,0 +1,84 @@
+use harsh::{harsh, DecodeError, Error, Harsh};
+
+const CHECKED: Harsh = harsh!(salt = "this is my salt", checksum = true);
+
+fn checked() -> Harsh {
+    Harsh::builder()
+        .salt("this is my salt")
+        .checksum(true)
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn check_character_is_appended() {
+    let plain = Harsh::builder().salt("this is my salt").build().unwrap();
+    let harsh = checked();
+
+    for values in &[&[1, 2, 3][..], &[0], &[u64::MAX, 42]] {
+        let hash = harsh.encode(values);
+
+        assert_eq!(plain.encode(values), hash[..hash.len() - 1]);
+        assert_eq!(&values[..], &harsh.decode(&hash).unwrap()[..]);
+    }
+}
+
+#[test]
+fn single_mistyped_characters_are_caught() {
+    let harsh = checked();
+    let alphabet = "abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ1234567890";
+
+    for value in 0..200 {
+        let hash = harsh.encode(&[value, 7]);
+
+        for idx in 0..hash.len() {
+            for c in alphabet
+                .chars()
+                .filter(|&c| c != hash.as_bytes()[idx] as char)
+            {
+                let mut typo = hash.clone();
+                typo.replace_range(idx..=idx, c.encode_utf8(&mut [0; 4]));
+
+                match harsh.decode(&typo) {
+                    Err(Error::Decode(DecodeError::Checksum)) => {}
+                    result => panic!("{} mistyped as {} gave {:?}", hash, typo, result),
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn checksum_works_with_padding_prefix_and_blocklist() {
+    let harsh = Harsh::builder()
+        .length(12)
+        .prefix("usr")
+        .blocklist(Vec::<String>::new())
+        .checksum(true)
+        .build()
+        .unwrap();
+
+    for value in 0..1000 {
+        let hash = harsh.encode(&[value]);
+
+        assert_eq!(17, hash.len());
+        assert_eq!(&[value], &harsh.decode(&hash).unwrap()[..]);
+    }
+}
+
+#[test]
+fn unchecked_hashid_is_rejected() {
+    let plain = Harsh::builder().salt("this is my salt").build().unwrap();
+    let hash = plain.encode(&[1, 2, 3]);
+
+    assert!(checked().decode(&hash).is_err());
+    assert!(matches!(
+        checked().decode(""),
+        Err(Error::Decode(DecodeError::Empty))
+    ));
+}
+
+#[test]
+fn macro_checksum_matches_builder() {
+    assert_eq!(checked().encode(&[1, 2, 3]), CHECKED.encode(&[1, 2, 3]));
+}
@@ -0,0 +1,92 @@
+#![cfg(feature = "cli")]
+
+use std::{
+    io::Write,
+    process::{Command, Output, Stdio},
+};
+
+fn harsh(args: &[&str], stdin: &str) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_harsh"))
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(stdin.as_bytes())
+        .unwrap();
+    child.wait_with_output().unwrap()
+}
+
+fn stdout(output: &Output) -> &str {
+    std::str::from_utf8(&output.stdout).unwrap()
+}
+
+#[test]
+fn encode_arguments() {
+    let output = harsh(&["encode", "1", "2", "3"], "");
+
+    assert_eq!(Some(0), output.status.code());
+    assert_eq!("o2fXhV\n", stdout(&output));
+}
+
+#[test]
+fn decode_lines_with_config() {
+    let output = harsh(
+        &["--salt", "this is my salt", "--length", "8", "decode"],
+        "GlaHquq0\n\n4o6Z7KqxE\n",
+    );
+
+    assert_eq!(Some(0), output.status.code());
+    assert_eq!("1 2 3\n1226198605112\n", stdout(&output));
+}
+
+#[test]
+fn encode_with_prefix() {
+    let output = harsh(&["--prefix", "usr", "encode", "1", "2", "3"], "");
+
+    assert_eq!(Some(0), output.status.code());
+    assert_eq!("usr_o2fXhV\n", stdout(&output));
+}
+
+#[test]
+fn hex_round_trip() {
+    let output = harsh(&["-s", "this is my salt", "encode-hex", "deadbeef"], "");
+    assert_eq!("kRNrpKlJ\n", stdout(&output));
+
+    let output = harsh(&["-s", "this is my salt", "decode-hex", "kRNrpKlJ"], "");
+    assert_eq!("deadbeef\n", stdout(&output));
+}
+
+#[test]
+fn json_output() {
+    let output = harsh(&["--json", "encode"], "1,2,3\nx\n");
+
+    assert_eq!(Some(4), output.status.code());
+    assert_eq!(
+        "{\"hashid\":\"o2fXhV\",\"values\":[1,2,3]}\n\
+         {\"error\":\"invalid digit found in string\",\"input\":\"x\"}\n",
+        stdout(&output)
+    );
+}
+
+#[test]
+fn decode_error_exit_code() {
+    let output = harsh(&["decode", "o2fXhV", "bogus"], "");
+
+    assert_eq!(Some(4), output.status.code());
+    assert_eq!("1 2 3\n", stdout(&output));
+}
+
+#[test]
+fn build_error_exit_code() {
+    let output = harsh(&["--alphabet", "abc", "encode", "1"], "");
+
+    assert_eq!(Some(3), output.status.code());
+    assert!(output.stdout.is_empty());
+}
@@ -0,0 +1,139 @@
+// Generates conformance vectors with hashids.js, the reference implementation
+// of hashids, for `tests/conformance.rs` to check harsh against.
+//
+//     npm install --no-save hashids@2.3.0
+//     node tests/conformance/generate.js
+//
+// This writes `hashids-js-<version>.jsonl` next to itself, named after the
+// version of the hashids package that produced it, and refuses to run with
+// any other version than the one pinned below.
+//
+// Each line is a JSON object with the `salt`, `alphabet` (null for the
+// default), minimum `length` and `values` passed to hashids.js, and the `hash`
+// it produced. The cases are drawn from a fixed seed, so running this again
+// with the same version of hashids.js produces the same file.
+
+'use strict';
+
+const fs = require('fs');
+const path = require('path');
+
+const VERSION = '2.3.0';
+
+// The package may not export its `package.json`, so it is found on disk.
+function installedVersion() {
+    let dir = path.dirname(require.resolve('hashids'));
+    while (!fs.existsSync(path.join(dir, 'package.json'))) {
+        dir = path.dirname(dir);
+    }
+    return JSON.parse(fs.readFileSync(path.join(dir, 'package.json'), 'utf8')).version;
+}
+
+const version = installedVersion();
+if (version !== VERSION) {
+    throw new Error(`hashids ${version} is installed, but ${VERSION} is pinned`);
+}
+
+const hashids = require('hashids');
+const Hashids = hashids.default || hashids;
+
+const CASES = 5000;
+const SEED = 0x68617368;
+
+const SEPARATORS = 'cfhistuCFHISTU';
+const PRINTABLE = Array.from({ length: 94 }, (_, idx) => String.fromCharCode(33 + idx)).join('');
+// Characters outside ASCII, including some beyond the Basic Multilingual
+// Plane, which hashids.js reads as whole code points.
+const UNICODE = 'àéîõüßçñøåæœ日本語のソルトあいうえおアイウエオ한국어Ωλπ😀🎉🚀';
+// Spaces are fine in a salt, unlike in an alphabet.
+const SALT_CHARACTERS = ' ' + PRINTABLE;
+
+// mulberry32, which is small and good enough to pick test cases with.
+let state = SEED;
+function random() {
+    state = (state + 0x6d2b79f5) | 0;
+    let t = Math.imul(state ^ (state >>> 15), 1 | state);
+    t = (t + Math.imul(t ^ (t >>> 7), 61 | t)) ^ t;
+    return ((t ^ (t >>> 14)) >>> 0) / 4294967296;
+}
+
+function integer(max) {
+    return Math.floor(random() * max);
+}
+
+function pick(characters, count) {
+    const chars = Array.from(characters);
+    for (let idx = chars.length - 1; idx > 0; idx--) {
+        const other = integer(idx + 1);
+        [chars[idx], chars[other]] = [chars[other], chars[idx]];
+    }
+    return chars.slice(0, count).join('');
+}
+
+function characters(source, length) {
+    const chars = Array.from(source);
+    return Array.from({ length }, () => chars[integer(chars.length)]).join('');
+}
+
+function salt() {
+    switch (integer(5)) {
+        case 0:
+            return '';
+        case 1:
+            return 'this is my salt';
+        case 2:
+            return characters(SALT_CHARACTERS + UNICODE, 1 + integer(40));
+        default:
+            return characters(SALT_CHARACTERS, 1 + integer(40));
+    }
+}
+
+function alphabet() {
+    switch (integer(10)) {
+        case 0:
+        case 1:
+        case 2:
+        case 3:
+            return null;
+        case 4:
+            // Every separator and two or three digits, which on one side of
+            // the boundary leaves too few characters for values to spare
+            // any for guards, so that they come from the separators instead.
+            return pick(SEPARATORS, 14) + pick('0123456789', 2 + integer(2));
+        case 5:
+            return pick(PRINTABLE + UNICODE, 16 + integer(PRINTABLE.length - 15));
+        default:
+            return pick(PRINTABLE, 16 + integer(PRINTABLE.length - 15));
+    }
+}
+
+function value() {
+    switch (integer(3)) {
+        case 0:
+            return integer(100);
+        case 1:
+            return integer(4294967296);
+        default:
+            return integer(2097152) * 4294967296 + integer(4294967296);
+    }
+}
+
+const lines = [];
+for (let idx = 0; idx < CASES; idx++) {
+    const testCase = {
+        salt: salt(),
+        alphabet: alphabet(),
+        length: integer(2) === 0 ? 0 : integer(50),
+        values: Array.from({ length: 1 + integer(8) }, value),
+    };
+
+    const hashids =
+        testCase.alphabet === null
+            ? new Hashids(testCase.salt, testCase.length)
+            : new Hashids(testCase.salt, testCase.length, testCase.alphabet);
+    testCase.hash = hashids.encode(testCase.values);
+
+    lines.push(JSON.stringify(testCase) + '\n');
+}
+
+fs.writeFileSync(path.join(__dirname, `hashids-js-${version}.jsonl`), lines.join(''));
@@ -0,0 +1,42 @@
+<?php
+
+// Generates conformance vectors with hashids/hashids, the PHP implementation
+// of hashids, for `tests/conformance.rs` to check harsh against.
+//
+// It encodes the same cases as a corpus written by `generate.js`, so the two
+// implementations are checked against each other as well as against harsh:
+//
+//     cd "$(mktemp -d)" && composer require hashids/hashids:5.0.2
+//     php /path/to/tests/conformance/generate.php < hashids-js-2.3.0.jsonl
+//
+// This writes `hashids-php-<version>.jsonl` next to itself, named after the
+// version of the hashids package that produced it, and refuses to run with
+// any other version than the one pinned below.
+
+declare(strict_types=1);
+
+require getcwd() . '/vendor/autoload.php';
+
+use Composer\InstalledVersions;
+use Hashids\Hashids;
+
+const VERSION = '5.0.2';
+
+$version = InstalledVersions::getPrettyVersion('hashids/hashids');
+if ($version !== VERSION) {
+    throw new RuntimeException("hashids/hashids $version is installed, but " . VERSION . ' is pinned');
+}
+
+$lines = [];
+while (($line = fgets(STDIN)) !== false) {
+    $case = json_decode($line, true, 512, JSON_THROW_ON_ERROR);
+
+    $hashids = $case['alphabet'] === null
+        ? new Hashids($case['salt'], $case['length'])
+        : new Hashids($case['salt'], $case['length'], $case['alphabet']);
+    $case['hash'] = $hashids->encode($case['values']);
+
+    $lines[] = json_encode($case, JSON_UNESCAPED_SLASHES | JSON_UNESCAPED_UNICODE | JSON_THROW_ON_ERROR) . "\n";
+}
+
+file_put_contents(__DIR__ . "/hashids-php-$version.jsonl", implode('', $lines));
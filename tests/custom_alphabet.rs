@@ -1,4 +1,4 @@
-use harsh::Harsh;
+use harsh::{BuildError, Harsh};
 
 const NUMBERS: [u64; 3] = [1, 2, 3];
 
@@ -7,6 +7,28 @@ fn bad_alphabet() {
     test_alphabet("cCsSfFhHuUiItT01", "should work with the worst alphabet");
 }
 
+#[test]
+fn three_characters_for_values() {
+    test_alphabet(
+        "cCsSfFhHuUiItT012",
+        "should work with guards taken from the alphabet",
+    );
+}
+
+#[test]
+fn one_character_for_values() {
+    assert!(
+        matches!(
+            Harsh::builder()
+                .alphabet("abcdefghijklmnop")
+                .separators("abcdefghijklmno")
+                .build(),
+            Err(BuildError::AlphabetLength)
+        ),
+        "should throw an error when separators leave one character"
+    );
+}
+
 #[test]
 fn separators_alphabet() {
     test_alphabet(
@@ -0,0 +1,117 @@
+use harsh::{DecodeError, Error, Harsh, HashId, HashIdKind, Sqids};
+
+fn decode_error(result: Result<Vec<u64>, Error>) -> DecodeError {
+    match result {
+        Err(Error::Decode(e)) => e,
+        result => panic!("decoded as {:?}", result),
+    }
+}
+
+#[test]
+fn empty() {
+    let harsh = Harsh::builder().salt("this is my salt").build().unwrap();
+    assert_eq!(DecodeError::Empty, decode_error(harsh.decode("")));
+
+    let prefixed = Harsh::builder().prefix("usr").build().unwrap();
+    assert_eq!(DecodeError::Empty, decode_error(prefixed.decode("usr_")));
+}
+
+#[test]
+fn character_outside_the_alphabet() {
+    let harsh = Harsh::builder().salt("this is my salt").build().unwrap();
+    let e = decode_error(harsh.decode("laH-quq"));
+
+    assert_eq!(
+        DecodeError::Character {
+            index: 3,
+            character: '-'
+        },
+        e
+    );
+    assert_eq!(Some(3), e.index());
+    assert_eq!(Some('-'), e.character());
+    assert_eq!(
+        "Found '-' at index 3, which is not in the alphabet",
+        e.to_string()
+    );
+}
+
+#[test]
+fn character_index_counts_bytes_and_prefix() {
+    let prefixed = Harsh::builder().prefix("usr").build().unwrap();
+    let e = decode_error(prefixed.decode("usr_éo2fXhV"));
+
+    assert_eq!(Some(4), e.index());
+    assert_eq!(Some('é'), e.character());
+
+    let e = decode_error(prefixed.decode("usr_o2fXh!V"));
+    assert_eq!(Some(9), e.index());
+}
+
+#[test]
+fn character_is_reported_before_checksum() {
+    let checked = Harsh::builder().checksum(true).build().unwrap();
+    let e = decode_error(checked.decode("o2f!XhV"));
+
+    assert_eq!(Some(3), e.index());
+}
+
+#[test]
+fn overflow() {
+    let harsh = Harsh::builder().salt("this is my salt").build().unwrap();
+    let hash = harsh.encode_u128(&[u128::MAX]);
+    assert_eq!(DecodeError::Overflow, decode_error(harsh.decode(hash)));
+}
+
+#[test]
+fn guards() {
+    let harsh = Harsh::builder().salt("this is my salt").build().unwrap();
+    assert_eq!(DecodeError::Guards, decode_error(harsh.decode("a")));
+}
+
+#[test]
+fn mismatch() {
+    let harsh = Harsh::builder().salt("this is my salt").build().unwrap();
+    let hash = harsh.encode(&[1, 2, 3]) + "ab";
+    assert_eq!(DecodeError::Mismatch, decode_error(harsh.decode(hash)));
+}
+
+#[test]
+fn count() {
+    struct User;
+
+    impl HashIdKind for User {
+        const SALT: &'static str = "";
+
+        fn harsh() -> &'static Harsh {
+            static HARSH: Harsh = harsh::harsh!();
+            &HARSH
+        }
+    }
+
+    let id = Harsh::default().encode(&[1, 2]);
+    assert_eq!(
+        Some(&DecodeError::Count),
+        id.parse::<HashId<User>>().unwrap_err().decode_error()
+    );
+}
+
+#[test]
+fn error_conversion() {
+    let e = Error::from(DecodeError::Empty);
+    assert_eq!(Some(&DecodeError::Empty), e.decode_error());
+    assert_eq!("Hashid is empty", e.to_string());
+    assert_eq!(None, Error::Buffer.decode_error());
+}
+
+#[test]
+fn sqids_character_outside_the_alphabet() {
+    let sqids = Sqids::default();
+    let e = match sqids.decode("86Rf0-7") {
+        Err(Error::Decode(e)) => e,
+        result => panic!("decoded as {:?}", result),
+    };
+
+    assert_eq!(Some(5), e.index());
+    assert_eq!(Some('-'), e.character());
+}
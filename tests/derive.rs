@@ -0,0 +1,106 @@
+#![cfg(feature = "derive")]
+
+use harsh::{DecodeError, Error, Harsh, Hashid};
+
+#[derive(Clone, Copy, Debug, PartialEq, Hashid)]
+struct TenantId(u32);
+
+#[derive(Clone, Copy, Debug, PartialEq, Hashid)]
+struct ItemRef {
+    tenant: TenantId,
+    project: u16,
+    item: u64,
+    archived: bool,
+}
+
+#[derive(Debug, PartialEq, Hashid)]
+struct Offset {
+    ledger: u8,
+    offset: i64,
+}
+
+#[derive(Debug, PartialEq, Hashid)]
+struct Pair<T>(T, T);
+
+#[derive(Debug, PartialEq, Hashid)]
+struct Nothing;
+
+fn decode_error<H: Hashid + std::fmt::Debug>(result: Result<H, Error>) -> DecodeError {
+    match result {
+        Err(Error::Decode(e)) => e,
+        result => panic!("decoded as {:?}", result),
+    }
+}
+
+const ITEM: ItemRef = ItemRef {
+    tenant: TenantId(7),
+    project: 3,
+    item: 1234,
+    archived: true,
+};
+
+#[test]
+fn fields_are_values_in_order() {
+    let harsh = Harsh::builder().salt("this is my salt").build().unwrap();
+
+    assert_eq!(harsh.encode(&[7, 3, 1234, 1]), harsh.encode_hashid(&ITEM));
+    assert_eq!(harsh.encode(&[7]), harsh.encode_hashid(&TenantId(7)));
+}
+
+#[test]
+fn struct_round_trip() {
+    let harsh = Harsh::builder().salt("this is my salt").build().unwrap();
+
+    let id = harsh.encode_hashid(&ITEM);
+    assert_eq!(ITEM, harsh.decode_hashid(&id).unwrap());
+
+    let offset = Offset {
+        ledger: 2,
+        offset: -15,
+    };
+    let id = harsh.encode_hashid(&offset);
+    assert_eq!(offset, harsh.decode_hashid(&id).unwrap());
+}
+
+#[test]
+fn generic_struct_round_trip() {
+    let harsh = Harsh::builder().salt("this is my salt").build().unwrap();
+    let pair = Pair(TenantId(1), TenantId(2));
+
+    let id = harsh.encode_hashid(&pair);
+    assert_eq!(harsh.encode(&[1, 2]), id);
+    assert_eq!(pair, harsh.decode_hashid(&id).unwrap());
+}
+
+#[test]
+fn unit_struct_has_no_values() {
+    assert_eq!("", Harsh::default().encode_hashid(&Nothing));
+}
+
+#[test]
+fn value_count_must_match() {
+    let harsh = Harsh::builder().salt("this is my salt").build().unwrap();
+
+    let e = decode_error(harsh.decode_hashid::<ItemRef, _>(harsh.encode(&[7, 3, 1234])));
+    assert_eq!(DecodeError::Count, e);
+
+    let e = decode_error(harsh.decode_hashid::<ItemRef, _>(harsh.encode(&[7, 3, 1234, 1, 0])));
+    assert_eq!(DecodeError::Count, e);
+}
+
+#[test]
+fn values_must_fit_their_fields() {
+    let harsh = Harsh::builder().salt("this is my salt").build().unwrap();
+
+    let e = decode_error(harsh.decode_hashid::<ItemRef, _>(harsh.encode(&[1 << 32, 3, 1234, 1])));
+    assert_eq!(DecodeError::Overflow, e);
+
+    let e = decode_error(harsh.decode_hashid::<ItemRef, _>(harsh.encode(&[7, 1 << 16, 1234, 1])));
+    assert_eq!(DecodeError::Overflow, e);
+
+    let e = decode_error(harsh.decode_hashid::<ItemRef, _>(harsh.encode(&[7, 3, 1234, 2])));
+    assert_eq!(DecodeError::Overflow, e);
+
+    let e = decode_error(harsh.decode_hashid::<Offset, _>(harsh.encode(&[256, 0])));
+    assert_eq!(DecodeError::Overflow, e);
+}
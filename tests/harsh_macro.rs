@@ -0,0 +1,53 @@
+use harsh::{harsh, Harsh, HarshBuilder};
+
+const DEFAULT: Harsh = harsh!();
+const SALTED: Harsh = harsh!(salt = "this is my salt", length = 12);
+const CUSTOM: Harsh = harsh!(
+    salt = "this is my salt",
+    alphabet = "abcdefghijklmnopqrstuvwxyz",
+    separators = "fu",
+);
+
+#[test]
+fn default_matches_builder() {
+    let harsh = Harsh::default();
+    for values in &[&[1u64][..], &[1, 2, 3], &[u64::MAX, 0]] {
+        assert_eq!(harsh.encode(values), DEFAULT.encode(values));
+    }
+}
+
+#[test]
+fn salted_matches_builder() {
+    assert_eq!("9LGlaHquq06D", SALTED.encode(&[1, 2, 3]));
+    assert_eq!(vec![1, 2, 3], SALTED.decode("9LGlaHquq06D").unwrap());
+}
+
+#[test]
+fn custom_matches_builder() {
+    let harsh = HarshBuilder::new()
+        .salt("this is my salt")
+        .alphabet("abcdefghijklmnopqrstuvwxyz")
+        .separators("fu")
+        .build()
+        .unwrap();
+
+    for values in &[&[1u64][..], &[1, 2, 3], &[u64::MAX, 0]] {
+        assert_eq!(harsh.encode(values), CUSTOM.encode(values));
+    }
+}
+
+#[test]
+fn duplicate_characters_match_builder() {
+    const HARSH: Harsh = harsh!(
+        salt = "this is my salt",
+        alphabet = "abcdefghijklmnopqrstuvwxyzab",
+    );
+
+    assert_eq!("qrhqiy", HARSH.encode(&[1, 2, 3]));
+}
+
+#[test]
+fn can_build_in_function() {
+    let harsh = harsh!(salt = "this is my salt");
+    assert_eq!("laHquq", harsh.encode(&[1, 2, 3]));
+}
@@ -0,0 +1,87 @@
+use std::sync::OnceLock;
+
+use harsh::{harsh, DecodeError, Error, Harsh, HarshBuilder, HashId, HashIdKind};
+
+fn base() -> HarshBuilder {
+    Harsh::builder().salt("this is my salt")
+}
+
+struct User;
+
+impl HashIdKind for User {
+    const SALT: &'static str = ":user";
+
+    fn harsh() -> &'static Harsh {
+        static HARSH: OnceLock<Harsh> = OnceLock::new();
+        HARSH.get_or_init(|| base().for_kind::<Self>().build().unwrap())
+    }
+}
+
+struct Order;
+
+impl HashIdKind for Order {
+    const SALT: &'static str = ":order";
+    const PREFIX: &'static str = "ord";
+
+    fn harsh() -> &'static Harsh {
+        static HARSH: OnceLock<Harsh> = OnceLock::new();
+        HARSH.get_or_init(|| base().length(8).for_kind::<Self>().build().unwrap())
+    }
+}
+
+struct Invoice;
+
+impl HashIdKind for Invoice {
+    const SALT: &'static str = ":invoice";
+
+    fn harsh() -> &'static Harsh {
+        static HARSH: Harsh = harsh!(salt = ["this is my salt", ":invoice"]);
+        &HARSH
+    }
+}
+
+#[test]
+fn display_uses_kind_salt() {
+    let harsh = Harsh::builder()
+        .salt("this is my salt:user")
+        .build()
+        .unwrap();
+    assert_eq!(harsh.encode(&[42]), HashId::<User>::new(42).to_string());
+}
+
+#[test]
+fn round_trip() {
+    for &value in &[0, 1, 42, u64::MAX] {
+        let id = HashId::<Order>::new(value);
+        assert_eq!(id, id.to_string().parse().unwrap());
+    }
+}
+
+#[test]
+fn other_kind_does_not_parse() {
+    for value in 0..1000 {
+        let id = HashId::<User>::new(value).to_string();
+        assert!(id.parse::<HashId<Order>>().is_err(), "{} parsed", id);
+    }
+}
+
+#[test]
+fn several_values_do_not_parse() {
+    let id = User::harsh().encode(&[1, 2]);
+    assert!(matches!(
+        id.parse::<HashId<User>>(),
+        Err(Error::Decode(DecodeError::Count))
+    ));
+}
+
+#[test]
+fn order_uses_kind_prefix() {
+    let id = HashId::<Order>::new(42).to_string();
+    assert!(id.starts_with("ord_"), "{}", id);
+}
+
+#[test]
+fn compile_time_kind_matches_runtime_kind() {
+    let harsh = base().for_kind::<Invoice>().build().unwrap();
+    assert_eq!(harsh.encode(&[42]), HashId::<Invoice>::new(42).to_string());
+}
@@ -0,0 +1,77 @@
+use harsh::{harsh, BuildError, DecodeError, Error, Harsh};
+
+const ORDERS: Harsh = harsh!(prefix = "ord", prefix_separator = '-');
+
+#[test]
+fn prefix_is_written_and_stripped() {
+    let harsh = Harsh::builder().prefix("usr").build().unwrap();
+
+    assert_eq!("usr_o2fXhV", harsh.encode(&[1, 2, 3]));
+    assert_eq!(&[1, 2, 3], &harsh.decode("usr_o2fXhV").unwrap()[..]);
+}
+
+#[test]
+fn missing_prefix_is_rejected() {
+    let harsh = Harsh::builder().prefix("usr").build().unwrap();
+
+    for input in &["o2fXhV", "usro2fXhV", "usr-o2fXhV", "usr"] {
+        match harsh.decode(input) {
+            Err(Error::Decode(DecodeError::Prefix)) => {}
+            result => panic!("{:?} decoded as {:?}", input, result),
+        }
+    }
+}
+
+#[test]
+fn prefix_of_another_kind_is_rejected() {
+    let users = Harsh::builder().prefix("usr").build().unwrap();
+    let orders = Harsh::builder().prefix("ord").build().unwrap();
+
+    match users.decode(orders.encode(&[1])) {
+        Err(Error::Decode(DecodeError::Prefix)) => {}
+        result => panic!("decoded as {:?}", result),
+    }
+}
+
+#[test]
+fn prefix_is_not_blocked() {
+    let harsh = Harsh::builder()
+        .prefix("shit")
+        .blocklist(Vec::<String>::new())
+        .build()
+        .unwrap();
+
+    assert_eq!("shit_o2fXhV", harsh.encode(&[1, 2, 3]));
+}
+
+#[test]
+fn prefix_separator_in_alphabet_is_rejected() {
+    assert!(matches!(
+        Harsh::builder().prefix("usr").prefix_separator('a').build(),
+        Err(BuildError::PrefixSeparator('a'))
+    ));
+}
+
+#[test]
+fn illegal_prefix_is_rejected() {
+    assert!(matches!(
+        Harsh::builder().prefix("us_r").build(),
+        Err(BuildError::Prefix('_'))
+    ));
+    assert!(matches!(
+        Harsh::builder().prefix("usé").build(),
+        Err(BuildError::Prefix('é'))
+    ));
+}
+
+#[test]
+fn macro_prefix_matches_builder() {
+    let harsh = Harsh::builder()
+        .prefix("ord")
+        .prefix_separator('-')
+        .build()
+        .unwrap();
+
+    assert_eq!(harsh.encode(&[1, 2, 3]), ORDERS.encode(&[1, 2, 3]));
+    assert_eq!(&[1, 2, 3], &ORDERS.decode("ord-o2fXhV").unwrap()[..]);
+}
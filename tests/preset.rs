@@ -0,0 +1,147 @@
+use harsh::{harsh, Harsh, Preset};
+
+const PRESETS: &[Preset] = &[
+    Preset::Crockford,
+    Preset::Base58,
+    Preset::Lowercase,
+    Preset::Unambiguous,
+];
+
+const CROCKFORD: Harsh = harsh!(salt = "this is my salt", preset = Preset::Crockford);
+
+fn build(preset: Preset) -> Harsh {
+    Harsh::builder()
+        .salt("this is my salt")
+        .preset(preset)
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn presets_round_trip() {
+    for &preset in PRESETS {
+        let harsh = build(preset);
+
+        for value in 0..1000 {
+            let hash = harsh.encode(&[value, u64::MAX - value]);
+
+            assert!(
+                hash.chars().all(|c| preset.alphabet().contains(c)),
+                "{:?} produced {}",
+                preset,
+                hash
+            );
+            assert_eq!(
+                &[value, u64::MAX - value],
+                &harsh.decode(&hash).unwrap()[..]
+            );
+        }
+    }
+}
+
+#[test]
+fn presets_round_trip_padded() {
+    for &preset in PRESETS {
+        let harsh = Harsh::builder().preset(preset).length(16).build().unwrap();
+
+        for value in 0..1000 {
+            let hash = harsh.encode(&[value]);
+
+            assert_eq!(16, hash.len());
+            assert_eq!(&[value], &harsh.decode(&hash).unwrap()[..]);
+        }
+    }
+}
+
+#[test]
+fn separators_are_in_alphabet() {
+    for &preset in PRESETS {
+        assert!(
+            preset
+                .separators()
+                .chars()
+                .all(|c| preset.alphabet().contains(c)),
+            "{:?}",
+            preset
+        );
+    }
+}
+
+#[test]
+fn ambiguous_characters_are_left_out() {
+    for &preset in &[Preset::Crockford, Preset::Base58, Preset::Unambiguous] {
+        for c in "0O1lI".chars() {
+            let alphabet = preset.alphabet();
+            assert!(
+                !(alphabet.contains(c) && alphabet.contains(lookalike(c))),
+                "{:?} includes both {} and {}",
+                preset,
+                c,
+                lookalike(c)
+            );
+        }
+    }
+}
+
+fn lookalike(c: char) -> char {
+    match c {
+        '0' => 'O',
+        'O' => '0',
+        '1' => 'l',
+        _ => '1',
+    }
+}
+
+#[test]
+fn crockford_decodes_lookalikes_and_lowercase() {
+    let harsh = build(Preset::Crockford);
+
+    for value in 0..1000 {
+        let hash = harsh.encode(&[value]);
+        let typed = hash
+            .to_lowercase()
+            .replace('0', "o")
+            .replace('1', if value % 2 == 0 { "l" } else { "I" });
+
+        assert_eq!(&[value], &harsh.decode(&typed).unwrap()[..], "{}", typed);
+    }
+}
+
+#[test]
+fn other_presets_do_not_fold() {
+    let harsh = build(Preset::Base58);
+    let hash = harsh.encode(&[1, 2, 3]);
+
+    assert!(harsh.decode(hash.to_lowercase()).is_err());
+}
+
+#[test]
+fn builder_alphabet_overrides_preset() {
+    let alphabet = "abcdefghijklmnopqrstuvwxyz";
+    let preset = Harsh::builder()
+        .alphabet(alphabet)
+        .preset(Preset::Crockford)
+        .build()
+        .unwrap();
+    // None of the preset's separators are in the alphabet.
+    let plain = Harsh::builder()
+        .alphabet(alphabet)
+        .separators("")
+        .build()
+        .unwrap();
+
+    assert_eq!(plain.encode(&[1, 2, 3]), preset.encode(&[1, 2, 3]));
+}
+
+#[test]
+fn macro_preset_matches_builder() {
+    let harsh = build(Preset::Crockford);
+
+    assert_eq!(harsh.encode(&[1, 2, 3]), CROCKFORD.encode(&[1, 2, 3]));
+    assert_eq!(
+        &[1, 2, 3],
+        &CROCKFORD
+            .decode(harsh.encode(&[1, 2, 3]).to_lowercase())
+            .unwrap()[..]
+    );
+}
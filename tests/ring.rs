@@ -0,0 +1,84 @@
+use harsh::{harsh, DecodeError, Error, Harsh, HarshRing};
+
+const V1: Harsh = harsh!(salt = "leaked salt");
+
+fn v2() -> Harsh {
+    Harsh::builder().salt("new salt").build().unwrap()
+}
+
+fn v3() -> Harsh {
+    Harsh::builder()
+        .salt("newer salt")
+        .length(8)
+        .build()
+        .unwrap()
+}
+
+fn ring() -> HarshRing {
+    HarshRing::new(3, v3()).fallback(2, v2()).fallback(1, V1)
+}
+
+#[test]
+fn encodes_with_primary() {
+    let ring = ring();
+
+    assert_eq!(3, ring.version());
+    assert_eq!(v3().encode(&[1, 2, 3]), ring.encode(&[1, 2, 3]));
+}
+
+#[test]
+fn decodes_every_version() {
+    let ring = ring();
+
+    for (version, harsh) in &[(3, v3()), (2, v2()), (1, V1)] {
+        for value in 0..100 {
+            let decoded = ring.decode(harsh.encode(&[value, 42])).unwrap();
+
+            assert_eq!(*version, decoded.version, "{}", value);
+            assert_eq!(vec![value, 42], decoded.values);
+            assert_eq!(*version == 3, decoded.is_primary);
+        }
+    }
+}
+
+#[test]
+fn old_hashids_redirect_to_canonical() {
+    let ring = ring();
+    let old = V1.encode(&[42]);
+
+    let decoded = ring.decode(&old).unwrap();
+    let canonical = ring.encode(&decoded.values);
+
+    assert_ne!(old, canonical);
+    assert!(ring.decode(&canonical).unwrap().is_primary);
+}
+
+#[test]
+fn unknown_hashids_report_primary_error() {
+    let ring = ring();
+
+    assert!(matches!(
+        ring.decode(""),
+        Err(Error::Decode(DecodeError::Empty))
+    ));
+
+    let other = Harsh::builder().salt("other salt").build().unwrap();
+    assert!(ring.decode(other.encode(&[1, 2, 3])).is_err());
+}
+
+#[test]
+fn get_by_version() {
+    let ring = ring();
+
+    assert_eq!(
+        V1.encode(&[1]),
+        ring.get(1).map(|harsh| harsh.encode(&[1])).unwrap()
+    );
+    assert!(ring.get(4).is_none());
+}
+
+#[test]
+#[should_panic(expected = "version 2 is already in the ring")]
+fn repeated_version_panics() {
+    let _ = HarshRing::new(2, v2()).fallback(2, V1);
+}
@@ -0,0 +1,131 @@
+#![cfg(feature = "serde")]
+
+use std::sync::OnceLock;
+
+use harsh::{serde::HarshSource, Harsh, HarshBuilder, HarshConfig, Preset};
+use serde::{Deserialize, Serialize};
+
+struct Ids;
+
+impl HarshSource for Ids {
+    fn harsh() -> &'static Harsh {
+        static HARSH: OnceLock<Harsh> = OnceLock::new();
+        HARSH.get_or_init(|| Harsh::builder().salt("this is my salt").build().unwrap())
+    }
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Provided {
+    #[serde(with = "harsh::serde::Using::<Ids>")]
+    id: u64,
+    name: String,
+}
+
+#[cfg(feature = "std")]
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct ThreadLocal {
+    #[serde(with = "harsh::serde::thread_local")]
+    id: u64,
+}
+
+#[test]
+fn config_from_json() {
+    let config: HarshConfig =
+        serde_json::from_str(r#"{ "salt": "this is my salt", "length": 8 }"#).unwrap();
+    let harsh = config.build().unwrap();
+
+    assert_eq!("GlaHquq0", harsh.encode(&[1, 2, 3]));
+}
+
+#[test]
+fn preset_from_json() {
+    let config: HarshConfig = serde_json::from_str(r#"{ "preset": "crockford" }"#).unwrap();
+    assert_eq!(Some(Preset::Crockford), config.preset);
+
+    let harsh = config.build().unwrap();
+    assert_eq!(
+        Harsh::builder()
+            .preset(Preset::Crockford)
+            .build()
+            .unwrap()
+            .encode(&[1, 2, 3]),
+        harsh.encode(&[1, 2, 3])
+    );
+}
+
+#[test]
+fn builder_round_trip() {
+    let builder = Harsh::builder()
+        .salt("this is my salt")
+        .alphabet("abcdefghijklmnopqrstuvwxyz")
+        .length(12);
+    let json = serde_json::to_string(&builder).unwrap();
+    let builder: HarshBuilder = serde_json::from_str(&json).unwrap();
+    let config: HarshConfig = serde_json::from_str(&json).unwrap();
+
+    assert_eq!(Some("this is my salt"), config.salt.as_deref());
+    assert_eq!(None, config.separators);
+    assert_eq!(
+        builder.build().unwrap().encode(&[1, 2, 3]),
+        config.build().unwrap().encode(&[1, 2, 3])
+    );
+}
+
+#[test]
+fn provided_harsh() {
+    let user = Provided {
+        id: 1226198605112,
+        name: "Ferris".into(),
+    };
+    let json = serde_json::to_string(&user).unwrap();
+
+    assert_eq!(r#"{"id":"4o6Z7KqxE","name":"Ferris"}"#, json);
+    assert_eq!(user, serde_json::from_str(&json).unwrap());
+}
+
+#[test]
+fn provided_harsh_rejects_invalid_hashids() {
+    let json = format!(
+        r#"{{"id":"{}","name":"Ferris"}}"#,
+        Ids::harsh().encode(&[1, 2])
+    );
+
+    assert!(serde_json::from_str::<Provided>(&json).is_err());
+    assert!(serde_json::from_str::<Provided>(r#"{"id":"nope","name":""}"#).is_err());
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn thread_local_harsh() {
+    std::thread::spawn(|| {
+        assert!(serde_json::to_string(&ThreadLocal { id: 1 }).is_err());
+
+        harsh::serde::set_thread_harsh(Harsh::default());
+        let json = serde_json::to_string(&ThreadLocal { id: 1 }).unwrap();
+
+        assert_eq!(r#"{"id":"jR"}"#, json);
+        assert_eq!(ThreadLocal { id: 1 }, serde_json::from_str(&json).unwrap());
+    })
+    .join()
+    .unwrap();
+}
+
+#[test]
+fn hashid_as_string() {
+    struct User;
+
+    impl harsh::HashIdKind for User {
+        const SALT: &'static str = ":user";
+
+        fn harsh() -> &'static Harsh {
+            static HARSH: Harsh = harsh::harsh!(salt = ["my salt", ":user"]);
+            &HARSH
+        }
+    }
+
+    let id = harsh::HashId::<User>::new(1);
+    let json = serde_json::to_string(&id).unwrap();
+
+    assert_eq!(format!("\"{}\"", id), json);
+    assert_eq!(id, serde_json::from_str(&json).unwrap());
+}
@@ -0,0 +1,127 @@
+use harsh::{DecodeError, Error, Harsh};
+
+fn share_list() -> Vec<u64> {
+    (0..40).map(|n| 1_000_000 + n * 37).collect()
+}
+
+#[test]
+fn set_round_trip() {
+    let harsh = Harsh::builder().salt("this is my salt").build().unwrap();
+    let cases: [&[u64]; 7] = [
+        &[],
+        &[0],
+        &[u64::MAX],
+        &[0, u64::MAX],
+        &[5, 3, 1],
+        &[1, 2, 3, 10, 11, 12, 13, 100],
+        &[u64::MAX - 2, u64::MAX - 1, u64::MAX],
+    ];
+
+    for &values in &cases {
+        let mut sorted = values.to_vec();
+        sorted.sort_unstable();
+
+        let hash = harsh.encode_set(values).unwrap();
+        assert_eq!(sorted, harsh.decode_set(&hash).unwrap(), "{:?}", values);
+    }
+}
+
+#[test]
+fn order_and_repeats_do_not_matter() {
+    let harsh = Harsh::builder().salt("this is my salt").build().unwrap();
+
+    assert_eq!(
+        harsh.encode_set(&[1, 5, 9]).unwrap(),
+        harsh.encode_set(&[9, 1, 5, 1, 9]).unwrap()
+    );
+    assert_eq!(
+        vec![1, 5, 9],
+        harsh
+            .decode_set(harsh.encode_set(&[9, 5, 5, 1]).unwrap())
+            .unwrap()
+    );
+}
+
+#[test]
+fn sets_are_shorter_than_plain_hashids() {
+    let harsh = Harsh::builder().salt("this is my salt").build().unwrap();
+    let values = share_list();
+
+    let set = harsh.encode_set(&values).unwrap();
+    let plain = harsh.encode(&values);
+    assert!(
+        set.len() * 2 < plain.len(),
+        "{} is not much shorter than {}",
+        set,
+        plain
+    );
+}
+
+#[test]
+fn ranges_are_run_length_encoded() {
+    let harsh = Harsh::builder().salt("this is my salt").build().unwrap();
+    let values: Vec<u64> = (1000..2000).chain(5000..6000).collect();
+
+    let hash = harsh.encode_set(&values).unwrap();
+    assert!(hash.len() < 20, "{}", hash);
+    assert_eq!(values, harsh.decode_set(&hash).unwrap());
+}
+
+#[test]
+fn plain_hashids_are_rejected() {
+    let harsh = Harsh::builder().salt("this is my salt").build().unwrap();
+
+    // Each of these is a set in the form encode_set uses, with a leading
+    // value of 2 * (n - 1) for the n - 1 values that follow it.
+    for values in [&[0][..], &[2, 5], &[4, 1, 3], &[6, 1, 1, 1], &[3, 1, 2]] {
+        assert!(
+            matches!(harsh.decode_set(harsh.encode(values)), Err(Error::Set)),
+            "{:?}",
+            values
+        );
+    }
+    assert!(matches!(
+        harsh.decode_set(harsh.encode(&share_list())),
+        Err(Error::Set)
+    ));
+    assert!(matches!(
+        harsh.decode_set(harsh.encode_bytes(&[1, 2, 3])),
+        Err(Error::Set)
+    ));
+}
+
+#[test]
+fn sets_do_not_decode_as_values() {
+    let harsh = Harsh::builder().salt("this is my salt").build().unwrap();
+
+    for values in [&[][..], &[1, 2, 3], &[1, 5, 9]] {
+        let hash = harsh.encode_set(values).unwrap();
+        assert!(matches!(
+            harsh.decode(&hash),
+            Err(Error::Decode(DecodeError::Mismatch))
+        ));
+        assert!(matches!(harsh.decode_bytes(&hash), Err(Error::Bytes)));
+    }
+}
+
+#[test]
+fn huge_sets_are_rejected() {
+    let harsh = Harsh::builder().salt("this is my salt").build().unwrap();
+    let values: Vec<u64> = (0..=1 << 16).collect();
+
+    assert!(matches!(harsh.encode_set(&values), Err(Error::Set)));
+
+    let largest = harsh.encode_set(&values[1..]).unwrap();
+    assert_eq!(&values[1..], &harsh.decode_set(&largest).unwrap()[..]);
+}
+
+#[test]
+fn tiny_alphabets_are_rejected() {
+    // Every character but two goes to the separators and guards.
+    let harsh = Harsh::builder()
+        .alphabet("cfhistuCFHISTU01")
+        .build()
+        .unwrap();
+
+    assert!(matches!(harsh.encode_set(&[1, 2, 3]), Err(Error::Set)));
+}
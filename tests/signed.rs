@@ -0,0 +1,131 @@
+use harsh::{DecodeError, Error, Harsh, Integer};
+
+#[test]
+fn zigzag_mapping() {
+    let cases: [(i64, u128); 7] = [
+        (0, 0),
+        (-1, 1),
+        (1, 2),
+        (-2, 3),
+        (2, 4),
+        (i64::MAX, u64::MAX as u128 - 1),
+        (i64::MIN, u64::MAX as u128),
+    ];
+
+    for &(value, unsigned) in &cases {
+        assert_eq!(unsigned, value.to_unsigned(), "{}", value);
+        assert_eq!(Some(value), i64::from_unsigned(unsigned), "{}", value);
+    }
+}
+
+#[test]
+fn negative_one_is_not_u64_max() {
+    let harsh = Harsh::builder().salt("this is my salt").build().unwrap();
+
+    let negative = harsh.encode_i64(&[-1]);
+    let max = harsh.encode(&[u64::MAX]);
+
+    assert_ne!(negative, max);
+    assert_eq!(vec![-1], harsh.decode_i64(&negative).unwrap());
+    assert_eq!(vec![u64::MAX], harsh.decode(&max).unwrap());
+}
+
+#[test]
+fn i64_round_trip() {
+    let harsh = Harsh::builder().salt("this is my salt").build().unwrap();
+    let cases: [&[i64]; 5] = [
+        &[0],
+        &[-1, 0, 1],
+        &[i64::MIN, i64::MAX],
+        &[-928728, 928728],
+        &[-1000000000000, 42, -42],
+    ];
+
+    for &values in &cases {
+        let hash = harsh.encode_i64(values);
+        assert_eq!(values, &harsh.decode_i64(&hash).unwrap()[..]);
+    }
+}
+
+#[test]
+fn signed_values_hash_as_zigzag() {
+    let harsh = Harsh::builder().salt("this is my salt").build().unwrap();
+
+    assert_eq!(
+        harsh.encode(&[0, 1, 2, 3]),
+        harsh.encode_i64(&[0, -1, 1, -2])
+    );
+}
+
+#[test]
+fn unsigned_hashids_decode_as_zigzag() {
+    let harsh = Harsh::builder().salt("this is my salt").build().unwrap();
+
+    let hash = harsh.encode(&[3, 4]);
+    assert_eq!(vec![-2, 2], harsh.decode_i64(&hash).unwrap());
+    assert_eq!(
+        vec![-2i32, 2],
+        harsh.decode_integers::<i32, _>(&hash).unwrap()
+    );
+}
+
+#[test]
+fn unsigned_values_match_u64() {
+    let harsh = Harsh::builder().salt("this is my salt").build().unwrap();
+
+    assert_eq!(
+        harsh.encode(&[1, 2, 3]),
+        harsh.encode_integers(&[1u8, 2, 3])
+    );
+    assert_eq!(
+        harsh.encode(&[1, 2, 3]),
+        harsh.encode_integers(&[1u32, 2, 3])
+    );
+    assert_eq!(
+        harsh.encode_u128(&[u128::MAX]),
+        harsh.encode_integers(&[u128::MAX])
+    );
+}
+
+#[test]
+fn every_primitive_round_trips() {
+    fn check<I: Integer + PartialEq + std::fmt::Debug>(values: &[I]) {
+        let harsh = Harsh::builder().salt("this is my salt").build().unwrap();
+        let hash = harsh.encode_integers(values);
+        assert_eq!(values, &harsh.decode_integers::<I, _>(&hash).unwrap()[..]);
+    }
+
+    check(&[0u8, u8::MAX]);
+    check(&[0u16, u16::MAX]);
+    check(&[0u32, u32::MAX]);
+    check(&[0u64, u64::MAX]);
+    check(&[0u128, u128::MAX]);
+    check(&[0usize, usize::MAX]);
+    check(&[i8::MIN, -1, 0, i8::MAX]);
+    check(&[i16::MIN, -1, 0, i16::MAX]);
+    check(&[i32::MIN, -1, 0, i32::MAX]);
+    check(&[i64::MIN, -1, 0, i64::MAX]);
+    check(&[i128::MIN, -1, 0, i128::MAX]);
+    check(&[isize::MIN, -1, 0, isize::MAX]);
+}
+
+#[test]
+fn values_that_do_not_map_back_are_rejected() {
+    let harsh = Harsh::builder().salt("this is my salt").build().unwrap();
+
+    let hash = harsh.encode_u128(&[u64::MAX as u128 + 1]);
+    assert!(matches!(
+        harsh.decode_i64(&hash),
+        Err(Error::Decode(DecodeError::Overflow))
+    ));
+
+    let hash = harsh.encode_i64(&[-129]);
+    assert!(matches!(
+        harsh.decode_integers::<i8, _>(&hash),
+        Err(Error::Decode(DecodeError::Overflow))
+    ));
+    assert!(matches!(
+        harsh.decode_integers::<u8, _>(&harsh.encode(&[256])),
+        Err(Error::Decode(DecodeError::Overflow))
+    ));
+}
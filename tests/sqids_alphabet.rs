@@ -0,0 +1,55 @@
+use harsh::{BuildError, Sqids};
+
+#[test]
+fn simple() {
+    let sqids = Sqids::builder()
+        .alphabet("0123456789abcdef")
+        .build()
+        .unwrap();
+
+    assert_eq!("489158", sqids.encode(&[1, 2, 3]).unwrap());
+    assert_eq!(&[1, 2, 3], &sqids.decode("489158").unwrap()[..]);
+}
+
+#[test]
+fn short_alphabet() {
+    test_alphabet("abc");
+}
+
+#[test]
+fn long_alphabet() {
+    test_alphabet(
+        "abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789!@#$%^&*()-_+|{}[];:'\"/?.>,<`~",
+    );
+}
+
+#[test]
+fn multibyte_characters() {
+    assert!(matches!(
+        Sqids::builder().alphabet("ë1092").build(),
+        Err(BuildError::IllegalCharacter('ë'))
+    ));
+}
+
+#[test]
+fn repeating_alphabet_characters() {
+    assert!(matches!(
+        Sqids::builder().alphabet("aabcdefg").build(),
+        Err(BuildError::DuplicateCharacter('a'))
+    ));
+}
+
+#[test]
+fn too_short_of_an_alphabet() {
+    assert!(matches!(
+        Sqids::builder().alphabet("ab").build(),
+        Err(BuildError::AlphabetLength)
+    ));
+}
+
+fn test_alphabet(alphabet: &str) {
+    let sqids = Sqids::builder().alphabet(alphabet).build().unwrap();
+    let id = sqids.encode(&[1, 2, 3]).unwrap();
+
+    assert_eq!(&[1, 2, 3], &sqids.decode(id).unwrap()[..], "{}", alphabet);
+}
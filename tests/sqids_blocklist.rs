@@ -0,0 +1,90 @@
+use harsh::{Error, Sqids};
+
+#[test]
+fn default_blocklist() {
+    let sqids = Sqids::default();
+
+    assert_eq!(&[4572721], &sqids.decode("aho1e").unwrap()[..]);
+    assert_eq!("JExTR", sqids.encode(&[4572721]).unwrap());
+}
+
+#[test]
+fn empty_blocklist() {
+    let sqids = Sqids::builder()
+        .blocklist(Vec::<String>::new())
+        .build()
+        .unwrap();
+
+    assert_eq!(&[4572721], &sqids.decode("aho1e").unwrap()[..]);
+    assert_eq!("aho1e", sqids.encode(&[4572721]).unwrap());
+}
+
+#[test]
+fn non_empty_blocklist() {
+    let sqids = Sqids::builder().blocklist(vec!["ArUO"]).build().unwrap();
+
+    assert_eq!(&[4572721], &sqids.decode("aho1e").unwrap()[..]);
+    assert_eq!("aho1e", sqids.encode(&[4572721]).unwrap());
+
+    assert_eq!(&[100000], &sqids.decode("ArUO").unwrap()[..]);
+    assert_eq!("QyG4", sqids.encode(&[100000]).unwrap());
+    assert_eq!(&[100000], &sqids.decode("QyG4").unwrap()[..]);
+}
+
+#[test]
+fn encode_blocklist() {
+    let sqids = Sqids::builder()
+        .blocklist(vec!["JSwXFaosAN", "OCjV9JK64o", "rBHf", "79SM", "7tE6"])
+        .build()
+        .unwrap();
+
+    assert_eq!("1aYeB7bRUt", sqids.encode(&[1_000_000, 2_000_000]).unwrap());
+    assert_eq!(
+        &[1_000_000, 2_000_000],
+        &sqids.decode("1aYeB7bRUt").unwrap()[..]
+    );
+}
+
+#[test]
+fn decode_blocklist() {
+    let sqids = Sqids::builder()
+        .blocklist(vec!["86Rf07", "se8ojk", "ARsz1p", "Q8AI49", "5sQRZO"])
+        .build()
+        .unwrap();
+
+    for id in &["86Rf07", "se8ojk", "ARsz1p", "Q8AI49", "5sQRZO"] {
+        assert_eq!(&[1, 2, 3], &sqids.decode(id).unwrap()[..]);
+    }
+}
+
+#[test]
+fn match_against_short_blocklist_word() {
+    let sqids = Sqids::builder().blocklist(vec!["pnd"]).build().unwrap();
+    let id = sqids.encode(&[1000]).unwrap();
+
+    assert_eq!(&[1000], &sqids.decode(id).unwrap()[..]);
+}
+
+#[test]
+fn blocklist_filtering_in_constructor() {
+    let sqids = Sqids::builder()
+        .alphabet("ABCDEFGHIJKLMNOPQRSTUVWXYZ")
+        .blocklist(vec!["sxnzkl"])
+        .build()
+        .unwrap();
+
+    assert_eq!("IBSHOZ", sqids.encode(&[1, 2, 3]).unwrap());
+    assert_eq!(&[1, 2, 3], &sqids.decode("IBSHOZ").unwrap()[..]);
+}
+
+#[test]
+fn max_encoding_attempts() {
+    let sqids = Sqids::builder()
+        .alphabet("abc")
+        .length(3)
+        .blocklist(vec!["cab", "abc", "bca"])
+        .build()
+        .unwrap();
+
+    assert!(matches!(sqids.encode(&[0]), Err(Error::Blocklist)));
+}
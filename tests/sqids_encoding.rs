@@ -0,0 +1,102 @@
+use harsh::Sqids;
+
+const INCREMENTAL_NUMBERS: [(&str, &[u64]); 10] = [
+    ("bM", &[0]),
+    ("Uk", &[1]),
+    ("gb", &[2]),
+    ("Ef", &[3]),
+    ("Vq", &[4]),
+    ("uw", &[5]),
+    ("OI", &[6]),
+    ("AX", &[7]),
+    ("p6", &[8]),
+    ("nJ", &[9]),
+];
+
+const INCREMENTAL_NUMBERS_SAME_INDEX_0: [(&str, &[u64]); 10] = [
+    ("SvIz", &[0, 0]),
+    ("n3qa", &[0, 1]),
+    ("tryF", &[0, 2]),
+    ("eg6q", &[0, 3]),
+    ("rSCF", &[0, 4]),
+    ("sR8x", &[0, 5]),
+    ("uY2M", &[0, 6]),
+    ("74dI", &[0, 7]),
+    ("30WX", &[0, 8]),
+    ("moxr", &[0, 9]),
+];
+
+const INCREMENTAL_NUMBERS_SAME_INDEX_1: [(&str, &[u64]); 10] = [
+    ("SvIz", &[0, 0]),
+    ("nWqP", &[1, 0]),
+    ("tSyw", &[2, 0]),
+    ("eX68", &[3, 0]),
+    ("rxCY", &[4, 0]),
+    ("sV8a", &[5, 0]),
+    ("uf2K", &[6, 0]),
+    ("7Cdk", &[7, 0]),
+    ("3aWP", &[8, 0]),
+    ("m2xn", &[9, 0]),
+];
+
+#[test]
+fn simple() {
+    test_cases(&[("86Rf07", &[1, 2, 3])]);
+}
+
+#[test]
+fn different_inputs() {
+    let values = [0, 0, 0, 1, 2, 3, 100, 1_000, 100_000, 1_000_000, u64::MAX];
+    test_round_trip(&values);
+}
+
+#[test]
+fn incremental_numbers() {
+    test_cases(&INCREMENTAL_NUMBERS);
+}
+
+#[test]
+fn incremental_numbers_same_index_0() {
+    test_cases(&INCREMENTAL_NUMBERS_SAME_INDEX_0);
+}
+
+#[test]
+fn incremental_numbers_same_index_1() {
+    test_cases(&INCREMENTAL_NUMBERS_SAME_INDEX_1);
+}
+
+#[test]
+fn multi_input() {
+    let values: Vec<u64> = (0..100).collect();
+    test_round_trip(&values);
+}
+
+#[test]
+fn encoding_no_numbers() {
+    assert_eq!("", Sqids::default().encode(&[]).unwrap());
+}
+
+#[test]
+fn decoding_empty_string() {
+    assert!(Sqids::default().decode("").unwrap().is_empty());
+}
+
+#[test]
+fn decoding_id_with_invalid_character() {
+    assert!(Sqids::default().decode("*").is_err());
+}
+
+fn test_cases(cases: &[(&str, &[u64])]) {
+    let sqids = Sqids::default();
+
+    for &(id, values) in cases {
+        assert_eq!(id, sqids.encode(values).unwrap());
+        assert_eq!(values, &sqids.decode(id).unwrap()[..]);
+    }
+}
+
+fn test_round_trip(values: &[u64]) {
+    let sqids = Sqids::default();
+    let id = sqids.encode(values).unwrap();
+    assert_eq!(values, &sqids.decode(id).unwrap()[..]);
+}
@@ -0,0 +1,76 @@
+use harsh::{BuildError, Sqids};
+
+const PADDED: &str = "86Rf07xd4zBmiJXQG6otHEbew02c3PWsUOLZxADhCpKj7aVFv9I8RquYrNlSTM";
+
+const INCREMENTAL: [(usize, &str); 12] = [
+    (6, "86Rf07"),
+    (7, "86Rf07x"),
+    (8, "86Rf07xd"),
+    (9, "86Rf07xd4"),
+    (10, "86Rf07xd4z"),
+    (11, "86Rf07xd4zB"),
+    (12, "86Rf07xd4zBm"),
+    (13, "86Rf07xd4zBmi"),
+    (62, PADDED),
+    (
+        63,
+        "86Rf07xd4zBmiJXQG6otHEbew02c3PWsUOLZxADhCpKj7aVFv9I8RquYrNlSTMy",
+    ),
+    (
+        64,
+        "86Rf07xd4zBmiJXQG6otHEbew02c3PWsUOLZxADhCpKj7aVFv9I8RquYrNlSTMyf",
+    ),
+    (
+        65,
+        "86Rf07xd4zBmiJXQG6otHEbew02c3PWsUOLZxADhCpKj7aVFv9I8RquYrNlSTMyf1",
+    ),
+];
+
+#[test]
+fn simple() {
+    let sqids = Sqids::builder().length(62).build().unwrap();
+
+    assert_eq!(PADDED, sqids.encode(&[1, 2, 3]).unwrap());
+    assert_eq!(&[1, 2, 3], &sqids.decode(PADDED).unwrap()[..]);
+}
+
+#[test]
+fn incremental() {
+    for &(length, id) in &INCREMENTAL {
+        let sqids = Sqids::builder().length(length).build().unwrap();
+
+        assert_eq!(id, sqids.encode(&[1, 2, 3]).unwrap());
+        assert_eq!(length, id.len());
+        assert_eq!(&[1, 2, 3], &sqids.decode(id).unwrap()[..]);
+    }
+}
+
+#[test]
+fn min_lengths() {
+    let cases: [&[u64]; 6] = [
+        &[0],
+        &[0, 0, 0, 0, 0],
+        &[1, 2, 3, 4, 5, 6, 7, 8, 9, 10],
+        &[100, 200, 300],
+        &[1_000, 2_000, 3_000],
+        &[1_000_000],
+    ];
+
+    for &length in &[0, 1, 5, 10, 62] {
+        let sqids = Sqids::builder().length(length).build().unwrap();
+
+        for &values in &cases {
+            let id = sqids.encode(values).unwrap();
+            assert!(id.len() >= length, "length too short for {}", length);
+            assert_eq!(values, &sqids.decode(id).unwrap()[..]);
+        }
+    }
+}
+
+#[test]
+fn out_of_range_min_length() {
+    assert!(matches!(
+        Sqids::builder().length(256).build(),
+        Err(BuildError::Length(256))
+    ));
+}
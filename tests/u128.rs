@@ -0,0 +1,53 @@
+use harsh::{DecodeError, Error, Harsh};
+
+const LARGE_VALUES: [u128; 5] = [
+    u64::MAX as u128 + 1,
+    340282366920938463463374607431768211,
+    0xdead_beef_0000_0000_cafe_babe_0000_0000,
+    u128::MAX - 1,
+    u128::MAX,
+];
+
+#[test]
+fn small_values_match_u64() {
+    let harsh = Harsh::builder().salt("this is my salt").build().unwrap();
+    let cases: [&[u64]; 4] = [&[0], &[1, 2, 3], &[u64::MAX], &[1226198605112, 0, 5]];
+
+    for &values in &cases {
+        let wide: Vec<u128> = values.iter().map(|&n| n as u128).collect();
+        let hash = harsh.encode(values);
+
+        assert_eq!(hash, harsh.encode_u128(&wide));
+        assert_eq!(wide, harsh.decode_u128(&hash).unwrap());
+    }
+}
+
+#[test]
+fn large_values() {
+    let harsh = Harsh::builder()
+        .salt("this is my salt")
+        .length(30)
+        .build()
+        .unwrap();
+
+    for &value in &LARGE_VALUES {
+        let hash = harsh.encode_u128(&[value, 1, value]);
+        let values = harsh.decode_u128(&hash).unwrap();
+
+        assert_eq!(&[value, 1, value], &values[..]);
+    }
+}
+
+#[test]
+fn large_values_overflow_u64() {
+    let harsh = Harsh::default();
+
+    for &value in &LARGE_VALUES {
+        let hash = harsh.encode_u128(&[value]);
+
+        assert!(matches!(
+            harsh.decode(&hash),
+            Err(Error::Decode(DecodeError::Overflow))
+        ));
+    }
+}
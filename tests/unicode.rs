@@ -0,0 +1,147 @@
+use harsh::{harsh, BuildError, Harsh};
+
+const HIRAGANA: &str = "あいうえおかきくけこさしすせそたちつてとなにぬねの";
+
+fn hiragana() -> Harsh {
+    Harsh::builder()
+        .salt("this is my salt")
+        .alphabet(HIRAGANA)
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn hiragana_round_trip() {
+    let harsh = hiragana();
+
+    for value in 0..1000 {
+        let hash = harsh.encode(&[value, 42]);
+        assert!(hash.chars().all(|c| HIRAGANA.contains(c)));
+        assert_eq!(&[value, 42], &harsh.decode(&hash).unwrap()[..]);
+    }
+}
+
+#[test]
+fn shuffle_uses_whole_code_points() {
+    // As produced by hashids.js, which salts its shuffles with `codePointAt`.
+    let harsh = hiragana();
+    assert_eq!("そついつうぬ", harsh.encode(&[1, 2, 3]));
+    assert_eq!("けそすのさ", harsh.encode(&[12345]));
+
+    let harsh = Harsh::builder()
+        .alphabet("abcdefghijklmnopABあ")
+        .build()
+        .unwrap();
+    assert_eq!("eglj", harsh.encode(&[1000]));
+}
+
+#[test]
+fn salt_uses_whole_code_points() {
+    // As produced by hashids.js, which reads its salt with `Array.from`.
+    let harsh = Harsh::builder().salt("sël").build().unwrap();
+    assert_eq!("OouzSd", harsh.encode(&[1, 2, 3]));
+    assert_eq!("n1mw", harsh.encode(&[12345]));
+    assert_eq!(vec![1, 2, 3], harsh.decode("OouzSd").unwrap());
+
+    let harsh = Harsh::builder().salt("日本語のソルト").build().unwrap();
+    assert_eq!("R3TGFM", harsh.encode(&[1, 2, 3]));
+    assert_eq!("KKY7", harsh.encode(&[12345]));
+    assert_eq!(vec![12345], harsh.decode("KKY7").unwrap());
+}
+
+#[test]
+fn macro_salt_uses_whole_code_points() {
+    const ACCENTED: Harsh = harsh!(salt = "sël");
+    assert_eq!("OouzSd", ACCENTED.encode(&[1, 2, 3]));
+
+    const JAPANESE: Harsh = harsh!(salt = ["日本語の", "ソルト"]);
+    assert_eq!("R3TGFM", JAPANESE.encode(&[1, 2, 3]));
+}
+
+#[test]
+fn length_is_counted_in_characters() {
+    let harsh = Harsh::builder()
+        .salt("this is my salt")
+        .alphabet(HIRAGANA)
+        .length(12)
+        .checksum(true)
+        .build()
+        .unwrap();
+
+    let hash = harsh.encode(&[1, 2, 3]);
+    assert_eq!(13, hash.chars().count());
+    assert_eq!(vec![1, 2, 3], harsh.decode(&hash).unwrap());
+}
+
+#[test]
+fn characters_sharing_a_low_byte_are_distinct() {
+    // `あ` is U+3042, which shares its low byte with `B`.
+    let harsh = Harsh::builder()
+        .alphabet("abcdefghijklmnopABあ")
+        .build()
+        .unwrap();
+
+    for value in 0..1000 {
+        let hash = harsh.encode(&[value]);
+        assert_eq!(vec![value], harsh.decode(&hash).unwrap());
+    }
+}
+
+#[test]
+fn encode_to_buf_counts_bytes() {
+    let harsh = hiragana();
+    let hash = harsh.encode(&[1, 2, 3]);
+
+    let mut buffer = [0; 64];
+    let len = harsh.encode_to_buf(&[1, 2, 3], &mut buffer).unwrap();
+    assert_eq!(hash.as_bytes(), &buffer[..len]);
+
+    let mut buffer = [0; 4];
+    assert!(harsh.encode_to_buf(&[1, 2, 3], &mut buffer).is_err());
+}
+
+#[test]
+fn macro_matches_builder() {
+    const HARSH: Harsh = harsh!(
+        salt = "this is my salt",
+        alphabet = "あいうえおかきくけこさしすせそたちつてとなにぬねの",
+    );
+
+    assert_eq!(hiragana().encode(&[1, 2, 3]), HARSH.encode(&[1, 2, 3]));
+}
+
+#[test]
+fn ascii_only_rejects_other_characters() {
+    let result = Harsh::builder().alphabet(HIRAGANA).ascii_only(true).build();
+    assert!(matches!(
+        result,
+        Err(BuildError::IllegalCharacter(c)) if HIRAGANA.contains(c)
+    ));
+
+    assert!(Harsh::builder().ascii_only(true).build().is_ok());
+}
+
+#[test]
+fn alphabet_too_long() {
+    let alphabet = ('\u{100}'..'\u{300}').collect::<String>();
+    let result = Harsh::builder().alphabet(alphabet).build();
+    assert!(matches!(result, Err(BuildError::AlphabetTooLong)));
+}
+
+#[test]
+fn invalid_utf8_alphabet() {
+    let mut alphabet = b"abcdefghijklmnop".to_vec();
+    alphabet.push(0xff);
+
+    let result = Harsh::builder().alphabet(alphabet).build();
+    assert!(matches!(
+        result,
+        Err(BuildError::IllegalCharacter(char::REPLACEMENT_CHARACTER))
+    ));
+}
+
+#[test]
+fn invalid_utf8_salt() {
+    let result = Harsh::builder().salt(vec![b's', 0xff]).build();
+    assert!(matches!(result, Err(BuildError::Salt)));
+}
@@ -0,0 +1,69 @@
+use harsh::{Error, Harsh};
+
+const UUIDS: [[u8; 16]; 4] = [
+    [0; 16],
+    [
+        0x67, 0xe5, 0x50, 0x44, 0x10, 0xb1, 0x42, 0x6f, 0x92, 0x47, 0xbb, 0x68, 0x0e, 0x5f, 0xe0,
+        0xc8,
+    ],
+    [
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x01,
+    ],
+    [0xff; 16],
+];
+
+#[test]
+fn uuid_round_trip() {
+    let harsh = Harsh::builder().salt("this is my salt").build().unwrap();
+
+    for uuid in &UUIDS {
+        let hash = harsh.encode_uuid(uuid);
+        let decoded: [u8; 16] = harsh.decode_uuid(&hash).unwrap();
+
+        assert_eq!(uuid, &decoded);
+    }
+}
+
+#[test]
+fn uuid_is_shorter_than_hex() {
+    let harsh = Harsh::default();
+    let hash = harsh.encode_uuid(&UUIDS[1]);
+    let hex = harsh
+        .encode_hex("67e5504410b1426f9247bb680e5fe0c8")
+        .unwrap();
+
+    assert!(
+        hash.len() < hex.len(),
+        "{} is not shorter than {}",
+        hash,
+        hex
+    );
+}
+
+#[test]
+fn uuid_requires_exactly_one_value() {
+    let harsh = Harsh::default();
+
+    assert!(matches!(
+        harsh.decode_uuid::<[u8; 16], _>(harsh.encode(&[1, 2])),
+        Err(Error::Uuid)
+    ));
+    assert!(matches!(
+        harsh.decode_uuid::<[u8; 16], _>("o2fXhV"),
+        Err(Error::Uuid)
+    ));
+}
+
+#[cfg(feature = "uuid")]
+#[test]
+fn uuid_type_round_trip() {
+    use uuid::Uuid;
+
+    let harsh = Harsh::default();
+    let uuid = Uuid::from_bytes(UUIDS[1]);
+    let hash = harsh.encode_uuid(&uuid);
+
+    assert_eq!(hash, harsh.encode_uuid(&UUIDS[1]));
+    assert_eq!(uuid, harsh.decode_uuid::<Uuid, _>(&hash).unwrap());
+}
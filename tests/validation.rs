@@ -0,0 +1,172 @@
+use harsh::{BuildError, BuildWarning, Harsh, Preset};
+
+#[test]
+fn duplicate_characters_are_ignored() {
+    let builder = Harsh::builder()
+        .salt("this is my salt")
+        .alphabet("abcdefghijklmnopqrstuvwxyzab");
+
+    assert_eq!(
+        vec![
+            BuildWarning::DuplicateCharacter('a'),
+            BuildWarning::DuplicateCharacter('b'),
+        ],
+        builder.lint()
+    );
+    // As produced by hashids.js, which also keeps only the first of each.
+    assert_eq!("qrhqiy", builder.build().unwrap().encode(&[1, 2, 3]));
+}
+
+#[test]
+fn characters_duplicated_by_case_folding_are_allowed() {
+    let harsh = Harsh::builder()
+        .alphabet("abcdefghijklmnopqrstuvwxyzABC")
+        .case_insensitive(true)
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        vec![1, 2, 3],
+        harsh.decode(harsh.encode(&[1, 2, 3])).unwrap()
+    );
+}
+
+#[test]
+fn invisible_characters_are_rejected() {
+    for &c in &[
+        ' ', '\t', '\n', '\0', '\u{7f}', '\u{a0}', '\u{200b}', '\u{feff}',
+    ] {
+        let alphabet = format!("abcdefghijklmnop{}", c);
+        let result = Harsh::builder().alphabet(alphabet).build();
+        assert!(
+            matches!(result, Err(BuildError::IllegalCharacter(found)) if found == c),
+            "{:?} should be rejected",
+            c
+        );
+    }
+}
+
+#[test]
+fn unknown_separators_are_ignored() {
+    let builder = Harsh::builder()
+        .salt("this is my salt")
+        .alphabet("abcdefghijklmnopqrstuvwxyz")
+        .separators("cfXhY");
+
+    assert_eq!(
+        vec![BuildWarning::IgnoredSeparators(vec!['X', 'Y'])],
+        builder.lint()
+    );
+    // As produced by hashids.js, which also drops them.
+    assert_eq!("tuhjcj", builder.build().unwrap().encode(&[1, 2, 3]));
+}
+
+#[test]
+fn all_unknown_separators_are_rejected() {
+    let result = Harsh::builder()
+        .alphabet("abcdefghijklmnopqrstuvwxyz")
+        .separators("XYZ")
+        .build();
+    assert!(matches!(result, Err(BuildError::Separator)));
+}
+
+#[test]
+fn missing_default_separators_are_ignored() {
+    assert!(Harsh::builder()
+        .alphabet("abcdefghijklmnopqrstuvwxyz")
+        .build()
+        .is_ok());
+    assert!(Harsh::builder()
+        .preset(Preset::Crockford)
+        .alphabet("abcdefghijklmnopqrstuvwxyz")
+        .build()
+        .is_ok());
+}
+
+#[test]
+fn separators_are_checked_after_case_folding() {
+    assert!(Harsh::builder()
+        .alphabet("abcdefghijklmnopqrstuvwxyz")
+        .separators("CFH")
+        .case_insensitive(true)
+        .build()
+        .is_ok());
+}
+
+#[test]
+fn absurd_lengths_are_rejected() {
+    assert!(Harsh::builder().length(4096).build().is_ok());
+    assert!(matches!(
+        Harsh::builder().length(4097).build(),
+        Err(BuildError::Length(4097))
+    ));
+    assert!(matches!(
+        Harsh::builder().length(usize::MAX).build(),
+        Err(BuildError::Length(usize::MAX))
+    ));
+}
+
+#[test]
+fn lint_default() {
+    assert_eq!(vec![BuildWarning::EmptySalt], Harsh::builder().lint());
+    assert_eq!(
+        Vec::<BuildWarning>::new(),
+        Harsh::builder().salt("this is my salt").lint()
+    );
+}
+
+#[test]
+fn lint_salt_whitespace() {
+    assert_eq!(
+        vec![BuildWarning::SaltWhitespace],
+        Harsh::builder().salt("this is my salt\n").lint()
+    );
+}
+
+#[test]
+fn lint_duplicate_separators() {
+    let builder = Harsh::builder()
+        .salt("this is my salt")
+        .separators("cfhcfc");
+
+    assert_eq!(
+        vec![
+            BuildWarning::DuplicateSeparator('c'),
+            BuildWarning::DuplicateSeparator('f'),
+        ],
+        builder.lint()
+    );
+    assert!(builder.build().is_ok());
+}
+
+#[test]
+fn lint_url_unsafe_characters() {
+    let builder = Harsh::builder()
+        .salt("this is my salt")
+        .alphabet("abcdefghijklmnop-_/?#%あ");
+
+    assert_eq!(
+        vec![
+            BuildWarning::UrlUnsafeCharacter('/'),
+            BuildWarning::UrlUnsafeCharacter('?'),
+            BuildWarning::UrlUnsafeCharacter('#'),
+            BuildWarning::UrlUnsafeCharacter('%'),
+            BuildWarning::UrlUnsafeCharacter('あ'),
+        ],
+        builder.lint()
+    );
+    assert!(builder.build().is_ok());
+}
+
+#[test]
+fn lint_presets() {
+    for &preset in &[
+        Preset::Crockford,
+        Preset::Base58,
+        Preset::Lowercase,
+        Preset::Unambiguous,
+    ] {
+        let builder = Harsh::builder().salt("this is my salt").preset(preset);
+        assert!(builder.lint().is_empty());
+    }
+}